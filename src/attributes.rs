@@ -0,0 +1,439 @@
+// src/attributes.rs
+//! Attribute rules for setup hooks along the class hierarchy.
+//!
+//! With super().setUp() called last, a subclass's `setUp` runs before its ancestors' have
+//! assigned anything. This module tracks the `self.<attr>` assignments of each setup hook
+//! along the MRO and flags, in a test class's hook, code before the super() call that
+//!
+//! - reads an attribute only an ancestor's hook assigns, which raises AttributeError as the
+//!   ancestor runs afterward;
+//! - assigns an attribute an ancestor's hook assigns too, which silently overwrites it.
+
+use crate::{
+    classes::{is_unknown_root, is_unresolved_root},
+    hierarchy::{calls_super, find_method},
+    index::{ClassRef, ProjectIndex},
+    is_async_hook,
+    mro::{self, MroEntry},
+    teardown::contains_super_call,
+    Checker, Label, Rule,
+};
+use ruff_python_ast::visitor::{walk_expr, walk_stmt, Visitor};
+use ruff_python_ast::{Expr, ExprAttribute, ExprContext, Stmt, StmtFunctionDef};
+use std::{
+    collections::{HashMap, HashSet},
+    slice,
+};
+
+/// The setup hooks whose attribute assignments are tracked
+const SETUP_HOOKS: &[&str] = &["setUp", "asyncSetUp", "setUpClass", "setUpTestData"];
+
+/// How a method uses an attribute of its `self` (or `cls`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AccessKind {
+    /// `self.user`
+    Read,
+    /// `self.user = ...`
+    Store,
+    /// `hasattr(self, "user")` or `getattr(self, "user", ...)`, which never raise
+    Probe,
+}
+
+/// A use of an attribute of a method's `self` (or `cls`).
+struct Access<'a> {
+    name: &'a str,
+    kind: AccessKind,
+    node: &'a Expr,
+}
+
+/// An ancestor's setup hook assigning an attribute.
+struct Assignment<'a> {
+    class: ClassRef<'a>,
+    /// The hook's first assignment of the attribute
+    node: &'a Expr,
+    /// Whether the hook looks at the attribute first, so as to keep a value already set
+    guarded: bool,
+}
+
+/// Check the attributes a test class's setup hooks use before calling super() against those
+/// its ancestors' hooks assign.
+pub(crate) fn check_setup_attributes<'a>(checker: &mut Checker<'a>, class: ClassRef<'a>) {
+    let index = checker.index;
+    let Some(mro) = mro::linearize(index, class) else {
+        return;
+    };
+
+    for &hook in SETUP_HOOKS {
+        let Some(func_def) = find_method(class.class_def, hook) else {
+            continue;
+        };
+        let Some(receiver) = receiver(func_def) else {
+            continue;
+        };
+        // Only the code before the super() call runs ahead of the ancestors; without a call
+        // they don't run at all, which the per-method check reports
+        let Some(call_index) = func_def
+            .body
+            .iter()
+            .position(|stmt| contains_super_call(slice::from_ref(stmt), hook))
+        else {
+            continue;
+        };
+
+        let ancestors = ancestor_assignments(index, &mro, hook, &checker.config.test_case_roots);
+        if ancestors.is_empty() {
+            continue;
+        }
+        let call = format!(
+            "{}super().{}()",
+            if is_async_hook(hook) { "await " } else { "" },
+            hook
+        );
+
+        let outer_method = checker.method.replace(hook);
+        let before_call = accesses(&func_def.body[..call_index], receiver);
+        let read_first = check_use_before_init(
+            checker,
+            receiver,
+            &mro,
+            hook,
+            &call,
+            &before_call,
+            &ancestors,
+        );
+
+        // Attributes already reported as read first aren't reported again, and an attribute
+        // assigned again after the call wins over the ancestors'
+        let mut settled = read_first;
+        settled.extend(
+            accesses(&func_def.body[call_index..], receiver)
+                .into_iter()
+                .filter(|access| access.kind == AccessKind::Store)
+                .map(|access| access.name),
+        );
+        check_clobbered(
+            checker,
+            receiver,
+            hook,
+            &call,
+            &before_call,
+            &settled,
+            &ancestors,
+        );
+        checker.method = outer_method;
+    }
+}
+
+/// Flag reads of attributes that only an ancestor's hook assigns, and return their names.
+fn check_use_before_init<'a>(
+    checker: &mut Checker,
+    receiver: &str,
+    mro: &[MroEntry],
+    hook: &str,
+    call: &str,
+    before_call: &[Access<'a>],
+    ancestors: &HashMap<&str, Vec<Assignment>>,
+) -> HashSet<&'a str> {
+    let provided = provided_elsewhere(mro, hook);
+    let mut assigned = HashSet::new();
+    let mut reported = HashSet::new();
+    for access in before_call {
+        match access.kind {
+            AccessKind::Store => {
+                assigned.insert(access.name);
+            }
+            AccessKind::Probe => {}
+            AccessKind::Read => {
+                if assigned.contains(access.name)
+                    || provided.contains(access.name)
+                    || !reported.insert(access.name)
+                {
+                    continue;
+                }
+                let Some(assignment) = ancestors.get(access.name).and_then(|found| found.first())
+                else {
+                    continue;
+                };
+                let source = &checker.index.files[assignment.class.file];
+                let label = assigned_here(checker, assignment, hook);
+                checker
+                    .report(
+                        Rule::UseBeforeInit,
+                        access.node,
+                        format_args!(
+                            "{}.{} is read before {} assigns it in {}.{} ({}:{})",
+                            receiver,
+                            access.name,
+                            call,
+                            assignment.class.class_def.name,
+                            hook,
+                            source.path.display(),
+                            source.line_index.line_number(assignment.node)
+                        ),
+                    )
+                    .labels
+                    .extend(label);
+            }
+        }
+    }
+    reported
+}
+
+/// Flag assignments that an ancestor's hook overwrites when the super() call runs it.
+fn check_clobbered(
+    checker: &mut Checker,
+    receiver: &str,
+    hook: &str,
+    call: &str,
+    before_call: &[Access],
+    settled: &HashSet<&str>,
+    ancestors: &HashMap<&str, Vec<Assignment>>,
+) {
+    let mut reported = HashSet::new();
+    for access in before_call {
+        if access.kind != AccessKind::Store
+            || settled.contains(access.name)
+            || !reported.insert(access.name)
+        {
+            continue;
+        }
+        // An ancestor that keeps a value already set doesn't overwrite it, but one after it
+        // in the MRO still may
+        let Some(assignment) = ancestors
+            .get(access.name)
+            .and_then(|found| found.iter().find(|assignment| !assignment.guarded))
+        else {
+            continue;
+        };
+        let source = &checker.index.files[assignment.class.file];
+        let label = assigned_here(checker, assignment, hook);
+        checker
+            .report(
+                Rule::AttributeOverwritten,
+                access.node,
+                format_args!(
+                    "{}.{} is overwritten by {}: {}.{} assigns it again ({}:{})",
+                    receiver,
+                    access.name,
+                    call,
+                    assignment.class.class_def.name,
+                    hook,
+                    source.path.display(),
+                    source.line_index.line_number(assignment.node)
+                ),
+            )
+            .labels
+            .extend(label);
+    }
+}
+
+/// Label an ancestor's assignment, if it is in the file being checked.
+fn assigned_here(checker: &Checker, assignment: &Assignment, hook: &str) -> Option<Label> {
+    (assignment.class.file == checker.file).then(|| {
+        checker.label(
+            assignment.node,
+            format_args!(
+                "{}.{} assigns it here",
+                assignment.class.class_def.name, hook
+            ),
+        )
+    })
+}
+
+/// Collect, for each attribute, the assignments of the `hook`s that a class's super() call
+/// reaches, in the order they run.
+fn ancestor_assignments<'a>(
+    index: &ProjectIndex<'a>,
+    mro: &[MroEntry<'a>],
+    hook: &str,
+    roots: &[String],
+) -> HashMap<&'a str, Vec<Assignment<'a>>> {
+    let mut assignments: HashMap<&str, Vec<Assignment>> = HashMap::new();
+    for entry in &mro[1..] {
+        // Classes outside the project aren't looked into, and TestCase classes end the chain
+        let ancestor = match entry {
+            MroEntry::Class(ancestor) => *ancestor,
+            MroEntry::Imported(name) if is_unresolved_root(name, roots) => break,
+            MroEntry::Unknown(name) if is_unknown_root(name, roots) => break,
+            _ => continue,
+        };
+        let Some(func_def) = find_method(ancestor.class_def, hook) else {
+            continue;
+        };
+
+        if let Some(receiver) = receiver(func_def) {
+            // A hook that looks at an attribute before assigning it may keep a value already set
+            let mut looked = HashSet::new();
+            let mut stored = HashSet::new();
+            for access in accesses(&func_def.body, receiver) {
+                if access.kind != AccessKind::Store {
+                    looked.insert(access.name);
+                } else if stored.insert(access.name) {
+                    assignments
+                        .entry(access.name)
+                        .or_default()
+                        .push(Assignment {
+                            class: ancestor,
+                            node: access.node,
+                            guarded: looked.contains(access.name),
+                        });
+                }
+            }
+        }
+        if !calls_super(index, ancestor.file, func_def, hook) {
+            break;
+        }
+    }
+    assignments
+}
+
+/// Collect the attributes that exist before `hook` runs anyway: those defined in the body of a
+/// class in the MRO, and those other methods assign.
+fn provided_elsewhere<'a>(mro: &[MroEntry<'a>], hook: &str) -> HashSet<&'a str> {
+    let mut provided = HashSet::new();
+    for entry in mro {
+        let MroEntry::Class(class) = entry else {
+            continue;
+        };
+        for stmt in &class.class_def.body {
+            match stmt {
+                Stmt::Assign(assign) => {
+                    provided.extend(assign.targets.iter().filter_map(|target| match target {
+                        Expr::Name(name) => Some(name.id.as_str()),
+                        _ => None,
+                    }));
+                }
+                Stmt::AnnAssign(assign) => {
+                    if let Expr::Name(name) = &*assign.target {
+                        provided.insert(name.id.as_str());
+                    }
+                }
+                Stmt::ClassDef(class_def) => {
+                    provided.insert(class_def.name.as_str());
+                }
+                Stmt::FunctionDef(func_def) => {
+                    provided.insert(func_def.name.as_str());
+                    if func_def.name.as_str() != hook {
+                        if let Some(receiver) = receiver(func_def) {
+                            provided.extend(
+                                accesses(&func_def.body, receiver)
+                                    .into_iter()
+                                    .filter(|access| access.kind == AccessKind::Store)
+                                    .map(|access| access.name),
+                            );
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    provided
+}
+
+/// Return the name of a method's first parameter, `self` or `cls` by convention.
+fn receiver(func_def: &StmtFunctionDef) -> Option<&str> {
+    let parameters = &func_def.parameters;
+    parameters
+        .posonlyargs
+        .iter()
+        .chain(&parameters.args)
+        .next()
+        .map(|param| param.parameter.name.as_str())
+}
+
+/// Collect the uses of `receiver`'s attributes in `body`, in the order they are evaluated.
+/// Nested functions, classes and lambdas run later, so they are skipped.
+fn accesses<'a>(body: &'a [Stmt], receiver: &str) -> Vec<Access<'a>> {
+    struct AccessCollector<'a, 'r> {
+        receiver: &'r str,
+        accesses: Vec<Access<'a>>,
+    }
+
+    impl<'a> AccessCollector<'a, '_> {
+        fn attribute(&self, expr: &'a Expr) -> Option<&'a ExprAttribute> {
+            match expr {
+                Expr::Attribute(attr) => match &*attr.value {
+                    Expr::Name(name) if name.id.as_str() == self.receiver => Some(attr),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+
+        /// Return the attribute `hasattr(self, "name")` or `getattr(self, "name", default)`
+        /// looks up. Without a default, `getattr` raises like any other read.
+        fn probe(&self, expr: &'a Expr) -> Option<&'a str> {
+            let Expr::Call(call) = expr else {
+                return None;
+            };
+            let Expr::Name(func) = &*call.func else {
+                return None;
+            };
+            match (func.id.as_str(), &*call.arguments.args) {
+                ("hasattr", [Expr::Name(object), Expr::StringLiteral(name)])
+                | ("getattr", [Expr::Name(object), Expr::StringLiteral(name), _])
+                    if object.id.as_str() == self.receiver =>
+                {
+                    Some(name.value.to_str())
+                }
+                _ => None,
+            }
+        }
+    }
+
+    impl<'a> Visitor<'a> for AccessCollector<'a, '_> {
+        fn visit_stmt(&mut self, stmt: &'a Stmt) {
+            match stmt {
+                Stmt::FunctionDef(_) | Stmt::ClassDef(_) => {}
+                // `self.count += 1` reads the attribute before assigning it
+                Stmt::AugAssign(aug_assign) => {
+                    self.visit_expr(&aug_assign.value);
+                    if let Some(attr) = self.attribute(&aug_assign.target) {
+                        self.accesses.push(Access {
+                            name: attr.attr.as_str(),
+                            kind: AccessKind::Read,
+                            node: &aug_assign.target,
+                        });
+                    }
+                    self.visit_expr(&aug_assign.target);
+                }
+                _ => walk_stmt(self, stmt),
+            }
+        }
+
+        fn visit_expr(&mut self, expr: &'a Expr) {
+            if let Expr::Lambda(_) = expr {
+                return;
+            }
+            if let Some(name) = self.probe(expr) {
+                self.accesses.push(Access {
+                    name,
+                    kind: AccessKind::Probe,
+                    node: expr,
+                });
+            }
+            let Some(attr) = self.attribute(expr) else {
+                walk_expr(self, expr);
+                return;
+            };
+            let kind = match attr.ctx {
+                ExprContext::Load => AccessKind::Read,
+                ExprContext::Store => AccessKind::Store,
+                _ => return,
+            };
+            self.accesses.push(Access {
+                name: attr.attr.as_str(),
+                kind,
+                node: expr,
+            });
+        }
+    }
+
+    let mut collector = AccessCollector {
+        receiver,
+        accesses: Vec::new(),
+    };
+    collector.visit_body(body);
+    collector.accesses
+}
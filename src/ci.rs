@@ -0,0 +1,126 @@
+// src/ci.rs
+//! Output for CI annotations: GitHub Actions workflow commands and GitLab Code Quality reports.
+//!
+//! GitHub turns `::error file=...,line=...::message` lines printed by a step into annotations
+//! on the changed lines. GitLab reads a Code Quality report, a JSON array of issues, and shows
+//! the issues a merge request introduces; it tells new issues from existing ones by their
+//! fingerprints, so a fingerprint must not change when unrelated edits move the violation to
+//! another line.
+
+use crate::{output::Run, Diagnostic, Severity};
+use serde_json::json;
+use std::{collections::HashMap, fmt::Write, path::Path};
+
+/// Render the diagnostics as GitHub Actions workflow commands, one per line.
+pub(crate) fn github(run: &Run) -> String {
+    let mut output = String::new();
+    for diagnostic in run.diagnostics {
+        let command = match diagnostic.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        let _ = writeln!(
+            output,
+            "::{} file={},line={},col={},endLine={},endColumn={},title={}::{}",
+            command,
+            escape_property(&path(&diagnostic.file)),
+            diagnostic.start.line,
+            diagnostic.start.column,
+            diagnostic.end.line,
+            diagnostic.end.column,
+            escape_property(&format!(
+                "{} ({})",
+                diagnostic.rule.code(),
+                diagnostic.rule.name()
+            )),
+            escape_data(&diagnostic.message),
+        );
+    }
+    output
+}
+
+/// Render the diagnostics as a GitLab Code Quality report.
+pub(crate) fn gitlab(run: &Run) -> String {
+    // Identical violations in the same method are told apart by their order
+    let mut occurrences = HashMap::new();
+    let issues: Vec<_> = run
+        .diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let path = path(&diagnostic.file);
+            let key = fingerprint_key(diagnostic, &path);
+            let occurrence = occurrences.entry(key.clone()).or_insert(0);
+            *occurrence += 1;
+            json!({
+                "type": "issue",
+                "check_name": diagnostic.rule.code(),
+                "description": format!("{} {}", diagnostic.rule.code(), diagnostic.message),
+                "categories": ["Bug Risk"],
+                "severity": match diagnostic.severity {
+                    Severity::Error => "major",
+                    Severity::Warning => "minor",
+                },
+                "fingerprint": fingerprint(&format!("{}\0{}", key, occurrence)),
+                "location": {
+                    "path": path,
+                    "lines": {"begin": diagnostic.start.line, "end": diagnostic.end.line},
+                },
+            })
+        })
+        .collect();
+    serde_json::to_string_pretty(&issues).unwrap_or_default()
+}
+
+/// Return what identifies a violation across runs: its rule, file, enclosing class and method
+/// and message, but not its position, nor the positions the message refers to.
+fn fingerprint_key(diagnostic: &Diagnostic, path: &str) -> String {
+    [
+        diagnostic.rule.code(),
+        path,
+        diagnostic.class.as_deref().unwrap_or_default(),
+        diagnostic.method.as_deref().unwrap_or_default(),
+        &without_line_numbers(&diagnostic.message),
+    ]
+    .join("\0")
+}
+
+/// Drop the line numbers of `(line 12)` and `(test_base.py:12)` references from `message`.
+fn without_line_numbers(message: &str) -> String {
+    let mut stripped = String::with_capacity(message.len());
+    let mut chars = message.chars().peekable();
+    while let Some(c) = chars.next() {
+        stripped.push(c);
+        if c == ':' || stripped.ends_with("line ") {
+            while chars.next_if(char::is_ascii_digit).is_some() {}
+        }
+    }
+    stripped
+}
+
+/// Hash `key` with 64-bit FNV-1a, which unlike the standard library's hashers is stable
+/// across releases and platforms.
+fn fingerprint(key: &str) -> String {
+    let hash = key.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Return `file` with forward slashes and no leading `./`, as both platforms expect
+/// repository-relative paths.
+fn path(file: &Path) -> String {
+    let path = file.to_string_lossy().replace('\\', "/");
+    path.trim_start_matches("./").to_string()
+}
+
+/// Escape a workflow command's message.
+fn escape_data(data: &str) -> String {
+    data.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow command's property value, which also can't contain `:` or `,`.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
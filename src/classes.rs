@@ -0,0 +1,200 @@
+// src/classes.rs
+//! Resolution of class bases within one module, to tell test classes from other classes.
+//!
+//! A class is a test class if one of its bases resolves, through the module's imports and its
+//! other classes, to one of the configured TestCase roots (`unittest.TestCase`,
+//! `django.test.TestCase`, ...). Imported bases are followed into the project's other files by
+//! the [`ProjectIndex`](crate::index::ProjectIndex); bases that still can't be resolved count
+//! if their name ends with `TestCase`.
+
+use crate::index::ClassRef;
+use ruff_python_ast::{ExceptHandler, Expr, Stmt, StmtClassDef};
+use std::collections::HashMap;
+
+/// The TestCase classes recognized out of the box.
+pub const DEFAULT_TEST_CASE_ROOTS: &[&str] = &[
+    "unittest.TestCase",
+    "unittest.IsolatedAsyncioTestCase",
+    "django.test.SimpleTestCase",
+    "django.test.TransactionTestCase",
+    "django.test.TestCase",
+    "django.test.LiveServerTestCase",
+];
+
+/// Check if a base that the project index can't resolve to a class, given by its qualified
+/// name, is one of the `roots`, or looks like a TestCase anyway.
+///
+/// The `*TestCase` fallback only applies to such bases: a class the index does resolve is a
+/// test class only if its own bases lead to a root, whatever it is named.
+pub(crate) fn is_unresolved_root(name: &str, roots: &[String]) -> bool {
+    roots.iter().any(|root| root == name) || name.ends_with("TestCase")
+}
+
+/// Check if a name the module doesn't define or import may stand for one of the `roots`:
+/// without an import, `TestCase` may be any root of that name.
+pub(crate) fn is_unknown_root(name: &str, roots: &[String]) -> bool {
+    is_unresolved_root(name, roots)
+        || roots.iter().any(|root| {
+            root.strip_suffix(name)
+                .is_some_and(|rest| rest.ends_with('.'))
+        })
+}
+
+/// What a base class expression refers to.
+#[derive(Debug)]
+pub(crate) enum Base<'a> {
+    /// A class defined in the project
+    Class(ClassRef<'a>),
+    /// An imported class, by qualified name (`unittest.TestCase`); relative imports keep their
+    /// leading dots (`.base.ApiTestCase`)
+    Imported(String),
+    /// A name the module doesn't define or import, as written
+    Unknown(String),
+}
+
+/// The imports and classes of one module.
+#[derive(Debug, Default)]
+pub(crate) struct ModuleScope<'a> {
+    /// The module's index in the project
+    file: usize,
+    /// Qualified names of imported names, by the name they are bound to
+    imports: HashMap<&'a str, String>,
+    /// Classes defined at module level, by name
+    classes: HashMap<&'a str, &'a StmtClassDef>,
+    /// Names of the imported modules, as written; `from a import b` lists `a.b` too, as `b`
+    /// may be a submodule
+    modules: Vec<String>,
+}
+
+impl<'a> ModuleScope<'a> {
+    /// Collect the imports and classes of a module body, including those inside top-level
+    /// `if` and `try` blocks (`if TYPE_CHECKING:`, `try: import ... except ImportError:`).
+    pub(crate) fn new(file: usize, body: &'a [Stmt]) -> Self {
+        let mut scope = ModuleScope {
+            file,
+            ..ModuleScope::default()
+        };
+        scope.collect(body);
+        scope
+    }
+
+    fn collect(&mut self, body: &'a [Stmt]) {
+        for stmt in body {
+            match stmt {
+                Stmt::Import(import) => {
+                    for alias in &import.names {
+                        let name = alias.name.as_str();
+                        match &alias.asname {
+                            // `import a.b as c` binds `c` to `a.b`
+                            Some(asname) => {
+                                self.imports.insert(asname.as_str(), name.to_string());
+                            }
+                            // `import a.b` binds `a`
+                            None => {
+                                let head = name.split('.').next().unwrap_or(name);
+                                self.imports.insert(head, head.to_string());
+                            }
+                        }
+                        // `import a.b` imports `a` as well
+                        for (index, _) in name.match_indices('.') {
+                            self.modules.push(name[..index].to_string());
+                        }
+                        self.modules.push(name.to_string());
+                    }
+                }
+                Stmt::ImportFrom(import) => {
+                    // Relative imports keep their leading dots
+                    let module = format!(
+                        "{}{}",
+                        ".".repeat(import.level as usize),
+                        import.module.as_ref().map_or("", |module| module.as_str())
+                    );
+                    for alias in &import.names {
+                        let name = alias.name.as_str();
+                        let bound = alias.asname.as_ref().map_or(name, |asname| asname.as_str());
+                        let qualified = if module.ends_with('.') {
+                            format!("{}{}", module, name)
+                        } else {
+                            format!("{}.{}", module, name)
+                        };
+                        self.imports.insert(bound, qualified.clone());
+                        self.modules.push(qualified);
+                    }
+                    self.modules.push(module);
+                }
+                Stmt::ClassDef(class_def) => {
+                    self.classes.insert(class_def.name.as_str(), class_def);
+                }
+                Stmt::If(if_stmt) => {
+                    self.collect(&if_stmt.body);
+                    for clause in &if_stmt.elif_else_clauses {
+                        self.collect(&clause.body);
+                    }
+                }
+                Stmt::Try(try_stmt) => {
+                    self.collect(&try_stmt.body);
+                    for handler in &try_stmt.handlers {
+                        let ExceptHandler::ExceptHandler(handler) = handler;
+                        self.collect(&handler.body);
+                    }
+                    self.collect(&try_stmt.orelse);
+                    self.collect(&try_stmt.finalbody);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Return the names of the modules this module imports, as written.
+    pub(crate) fn modules(&self) -> &[String] {
+        &self.modules
+    }
+
+    /// Return the class defined at module level as `name`.
+    pub(crate) fn class(&self, name: &str) -> Option<ClassRef<'a>> {
+        self.classes.get(name).map(|&class_def| ClassRef {
+            file: self.file,
+            class_def,
+        })
+    }
+
+    /// Return the classes defined at module level.
+    pub(crate) fn classes(&self) -> impl Iterator<Item = ClassRef<'a>> + '_ {
+        self.classes.values().map(|&class_def| ClassRef {
+            file: self.file,
+            class_def,
+        })
+    }
+
+    /// Return the qualified name the module imports as `name`.
+    pub(crate) fn import(&self, name: &str) -> Option<&str> {
+        self.imports.get(name).map(String::as_str)
+    }
+
+    /// Resolve a base class expression such as `TestCase` or `unittest.TestCase`.
+    pub(crate) fn resolve(&self, expr: &Expr) -> Option<Base<'a>> {
+        match expr {
+            Expr::Name(name) => {
+                let id = name.id.as_str();
+                Some(if let Some(qualified) = self.import(id) {
+                    Base::Imported(qualified.to_string())
+                } else if let Some(class) = self.class(id) {
+                    Base::Class(class)
+                } else {
+                    Base::Unknown(id.to_string())
+                })
+            }
+            Expr::Attribute(attr) => Some(match self.resolve(&attr.value)? {
+                Base::Imported(module) => Base::Imported(format!("{}.{}", module, attr.attr)),
+                Base::Unknown(module) => Base::Unknown(format!("{}.{}", module, attr.attr)),
+                // Nested classes aren't tracked
+                Base::Class(class) => {
+                    Base::Unknown(format!("{}.{}", class.class_def.name, attr.attr))
+                }
+            }),
+            // `Generic[T]` and other subscripted bases
+            Expr::Subscript(subscript) => self.resolve(&subscript.value),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,95 @@
+// src/diagnostic.rs
+//! Violations as structured data.
+//!
+//! Every check reports a [`Diagnostic`]; the CLI's text output, `path:line:column CODE message`, is
+//! just its [`Display`](fmt::Display) form, so tools can consume the fields instead of parsing
+//! text.
+
+use crate::rules::{Rule, Severity};
+use serde::Serialize;
+use std::{fmt, path::PathBuf};
+
+/// A violation of a rule at a span of a file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    /// The rule violated
+    pub rule: Rule,
+    pub severity: Severity,
+    /// The file, as given or relative to the working directory
+    pub file: PathBuf,
+    /// Where the offending code starts
+    pub start: Location,
+    /// Where the offending code ends, exclusive
+    pub end: Location,
+    pub message: String,
+    /// Related code in the same file, such as the misplaced super() call
+    pub labels: Vec<Label>,
+    /// What `--fix` changes to resolve the violation, if it can
+    pub fix: Option<Fix>,
+    /// The class the violation is in, if any
+    pub class: Option<String>,
+    /// The method or function the violation is about, if any
+    pub method: Option<String>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{} {} {}",
+            self.file.display(),
+            self.start.line,
+            self.start.column,
+            self.rule.code(),
+            self.message
+        )
+    }
+}
+
+/// A position in a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Location {
+    /// The one-based line
+    pub line: usize,
+    /// The one-based column, in characters
+    pub column: usize,
+}
+
+/// A secondary span of a violation, with what it has to do with it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Label {
+    pub start: Location,
+    /// Where the labelled code ends, exclusive
+    pub end: Location,
+    /// What the code is, e.g. "super().setUp() found here"
+    pub message: String,
+}
+
+/// A fix `--fix` applies for a violation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Fix {
+    /// What the fix changes, e.g. "Rename to setUp"
+    pub message: String,
+    /// The replacements `--fix` makes, one for each function it rewrites; a function with
+    /// several violations is rewritten once, so their fixes share the edit
+    pub edits: Vec<Edit>,
+}
+
+impl Fix {
+    pub(crate) fn new(message: impl fmt::Display) -> Self {
+        Fix {
+            message: message.to_string(),
+            edits: Vec::new(),
+        }
+    }
+}
+
+/// A replacement of a span of a file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Edit {
+    pub start: Location,
+    /// Where the replaced text ends, exclusive
+    pub end: Location,
+    /// The text replacing the span
+    pub content: String,
+}
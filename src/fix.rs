@@ -2,13 +2,44 @@
 //! Automatic fixing of setUp/tearDown method violations.
 //!
 //! This module provides functionality to automatically fix:
-//! - Incorrect method naming (setup -> setUp, teardown -> tearDown)
-//! - Missing super() calls (adds them as the last statement)
+//! - Incorrect method naming (setup -> setUp, teardown -> tearDown, setupClass -> setUpClass)
+//! - Missing or misplaced super() calls (moves them first or last, as configured)
+//! - Missing @classmethod decorators on setUpClass/tearDownClass/setUpTestData
+//! - asyncSetUp/asyncTearDown declared without `async def` or without awaiting super()
+//! - Near-miss spellings of pytest xunit hooks (setupMethod -> setup_method)
+//! - Misspelled unittest module fixtures (setupModule / setup_module -> setUpModule)
+//! - Legacy `super(Cls, self).setUp()` / `Base.setUp(self)` calls, when opted into
+//! - tearDowns whose cleanup can raise before super(), by wrapping it in `try:` / `finally:`,
+//!   when opted into
+//!
+//! Each function the fixes touch is rewritten as a whole, so the changes to a file are one
+//! [`Edit`] per function, replacing its lines. The checker attaches them to the diagnostics
+//! they resolve, for formats such as SARIF that carry the fix itself.
 
-use ruff_python_ast::Stmt;
-use ruff_python_parser::parse_module;
+use crate::{
+    diagnostic::{Edit, Location},
+    flow,
+    index::{ClassRef, Project, ProjectIndex},
+    is_async_hook, is_class_fixture, is_classmethod, is_docstring, is_miscased, is_unittest_module,
+    line_index::LineIndex,
+    module_fixture_misspelling, super_call, teardown, xunit, Config, Rule, SuperForm,
+    SuperPosition,
+};
+use ruff_python_ast::{Stmt, StmtClassDef, StmtFunctionDef};
 use ruff_text_size::Ranged;
-use std::{fs, path::Path};
+use std::{
+    fs, io, mem,
+    path::{Path, PathBuf},
+};
+
+/// What fixing a set of files did.
+#[derive(Debug, Default)]
+pub struct FixOutcome {
+    /// The files that were rewritten
+    pub fixed: Vec<PathBuf>,
+    /// The files that needed fixing but couldn't be written, and why
+    pub errors: Vec<(PathBuf, io::Error)>,
+}
 
 /// Attempt to fix setUp/tearDown violations in a Python file.
 ///
@@ -20,133 +51,448 @@ use std::{fs, path::Path};
 ///
 /// # Modifications
 /// - Renames incorrectly-cased methods (setup -> setUp, teardown -> tearDown)
-/// - Adds super().setUp()/super().tearDown() as the last statement if missing
+/// - Adds super().setUp()/super().tearDown() as the last statement if missing or misplaced
+/// - Adds @classmethod to setUpClass/tearDownClass/setUpTestData if missing
+/// - Adds `await super().asyncSetUp()` and `async def` to asyncSetUp/asyncTearDown
+/// - Renames near-miss pytest xunit hooks (setupMethod -> setup_method)
+/// - Renames misspelled module fixtures in unittest modules (setup_module -> setUpModule)
 /// - Preserves all other code and formatting
 pub fn fix_file(path: &Path) -> bool {
-    let Ok(src) = fs::read_to_string(path) else {
-        return false;
-    };
-    let Ok(parsed) = parse_module(&src) else {
-        return false;
-    };
+    fix_file_with_config(path, &Config::default())
+}
 
-    // Convert source to lines for manipulation
-    let mut lines: Vec<String> = src.lines().map(|s| s.to_string()).collect();
-    let mut modified = false;
+/// Attempt to fix setUp/tearDown violations in a Python file using the given options.
+///
+/// super() calls are moved to the position given by [`Config::super_position`]. With
+/// [`Config::require_zero_arg_super`], this also rewrites `super(Cls, self).setUp()` and
+/// `Base.setUp(self)` to `super().setUp()` where that reaches the same method, and with
+/// [`Config::require_robust_teardown`] it moves the super() call of a tearDown whose cleanup
+/// can raise into a `finally:` block. Only violations of enabled rules ([`Config::rules`]) are
+/// fixed.
+pub fn fix_file_with_config(path: &Path, config: &Config) -> bool {
+    !fix_files_with_config(&[path], config).fixed.is_empty()
+}
 
-    // Recursively fix all top-level statements (including classes)
-    for stmt in parsed.syntax().body.iter() {
-        if fix_stmt(stmt, &src, &mut lines) {
-            modified = true;
+/// Attempt to fix setUp/tearDown violations in several Python files using the given options.
+///
+/// The files, and the project files they import, are parsed once up front, so that test
+/// classes deriving from a base class in another file are recognized. Only the given files are
+/// rewritten. Returns the paths of those that were modified, and of those that couldn't be
+/// written back.
+pub fn fix_files_with_config(paths: &[&Path], config: &Config) -> FixOutcome {
+    let project = Project::load(paths);
+    let index = ProjectIndex::new(&project);
+
+    let mut outcome = FixOutcome::default();
+    for file in 0..project.given {
+        let path = index.files[file].path.clone();
+        match fix_source(&index, file, config) {
+            Ok(true) => outcome.fixed.push(path),
+            Ok(false) => {}
+            Err(err) => outcome.errors.push((path, err)),
         }
     }
+    outcome
+}
 
-    // Write back the fixed content if any modifications were made
-    if modified {
-        fs::write(path, lines.join("\n")).ok();
+/// Fix one file of the project index, writing it back if anything changed. Returns whether
+/// it was rewritten.
+fn fix_source(index: &ProjectIndex, file: usize, config: &Config) -> io::Result<bool> {
+    // Rewriting a file that couldn't be parsed or decoded cleanly could lose code
+    if !index.files[file].errors.is_empty() {
+        return Ok(false);
+    }
+    let fixer = run_fixes(index, file, config);
+    if fixer.edits.is_empty() {
+        return Ok(false);
     }
 
-    modified
+    // Write back the fixed content, keeping the final newline that splitting into lines drops
+    let mut fixed = fixer.lines.join("\n");
+    if fixer.src.ends_with('\n') {
+        fixed.push('\n');
+    }
+    fs::write(&index.files[file].path, fixed)?;
+    Ok(true)
+}
+
+/// Return the edits `--fix` would make to one file of the project index, in reverse order.
+pub(crate) fn source_edits(index: &ProjectIndex, file: usize, config: &Config) -> Vec<Edit> {
+    run_fixes(index, file, config).edits
+}
+
+/// Fix one file of the project index in memory.
+fn run_fixes<'a>(index: &'a ProjectIndex<'a>, file: usize, config: &'a Config) -> Fixer<'a> {
+    let source = &index.files[file];
+    let body = &source.module.body;
+
+    // Convert source to lines for manipulation
+    let mut fixer = Fixer {
+        src: &source.src,
+        line_index: &source.line_index,
+        config,
+        unittest_module: is_unittest_module(index, file, &config.test_case_roots),
+        index,
+        file,
+        class: None,
+        test_case: false,
+        lines: source.src.lines().map(|s| s.to_string()).collect(),
+        edits: Vec::new(),
+    };
+
+    // Recursively fix all top-level statements (including classes). Statements are visited
+    // bottom-up so that inserted lines never shift the line numbers of statements still to fix.
+    for stmt in body.iter().rev() {
+        fix_stmt(&mut fixer, stmt);
+    }
+    fixer
+}
+
+/// State shared by the fixes while rewriting one file.
+struct Fixer<'a> {
+    src: &'a str,
+    /// Where the lines of `src` start
+    line_index: &'a LineIndex,
+    config: &'a Config,
+    /// Whether the file is written against unittest rather than plain pytest
+    unittest_module: bool,
+    /// The project's files, for resolving class bases
+    index: &'a ProjectIndex<'a>,
+    /// The index of the file being fixed in the project
+    file: usize,
+    /// The class whose body is being fixed, if any
+    class: Option<&'a StmtClassDef>,
+    /// Whether that class is a unittest-style test class
+    test_case: bool,
+    /// The file's lines, as rewritten so far
+    lines: Vec<String>,
+    /// The rewritten functions so far, bottom-up
+    edits: Vec<Edit>,
+}
+
+impl Fixer<'_> {
+    /// Record the rewrite of the zero-based lines `first..=last` of the original source, which
+    /// were `len` lines long before.
+    fn record_edit(&mut self, first: usize, last: usize, len: usize) {
+        // Everything above `first` is still as it was, as statements are fixed bottom-up
+        let new_last = last + self.lines.len() - len;
+        let end = self.line_index.line_end(self.src, last);
+        self.edits.push(Edit {
+            start: Location {
+                line: first + 1,
+                column: 1,
+            },
+            end: self.line_index.location(self.src, end),
+            content: self.lines[first..=new_last].join("\n"),
+        });
+    }
 }
 
 /// Recursively fix a statement for setUp/tearDown violations.
 ///
-/// - For function definitions: fixes method name and adds/corrects super() calls
-/// - For class definitions: recursively fixes all methods in the class
-fn fix_stmt(stmt: &Stmt, src: &str, lines: &mut Vec<String>) -> bool {
+/// - For methods of test classes: fixes method name and adds/corrects super() calls
+/// - For other functions: renames misspelled module fixtures and pytest xunit hooks
+/// - For class definitions: recursively fixes the methods of test classes
+fn fix_stmt<'a>(fixer: &mut Fixer<'a>, stmt: &'a Stmt) -> bool {
     match stmt {
         Stmt::FunctionDef(func_def) => {
-            let (correct_call, correct_name) = match func_def.name.as_str() {
-                "setUp" => ("super().setUp()", None),
-                "tearDown" => ("super().tearDown()", None),
-                "setup" => ("super().setUp()", Some("setUp")),
-                "teardown" => ("super().tearDown()", Some("tearDown")),
-                _ => return false,
+            // The function starts at its first decorator
+            let first = fixer.line_index.line(stmt.start().to_usize());
+            let last = fixer.line_index.line(stmt.end().to_usize());
+            let len = fixer.lines.len();
+            let modified = if fixer.test_case {
+                fix_method(fixer, stmt, func_def)
+            } else {
+                fix_hook_name(fixer, func_def)
             };
-
-            if func_def.body.is_empty() {
-                return false;
+            if modified {
+                fixer.record_edit(first, last, len);
             }
+            modified
+        }
+        Stmt::ClassDef(class_def) => {
+            let test_case = fixer.index.is_test_class(
+                ClassRef {
+                    file: fixer.file,
+                    class_def,
+                },
+                &fixer.config.test_case_roots,
+            );
+            // pytest collects plain `Test*` classes, which can only use its xunit hooks
+            let pytest_class = !test_case && class_def.name.as_str().starts_with("Test");
 
-            // Don't add super() to methods that only have pass
-            if func_def.body.len() == 1 {
-                if let Stmt::Pass(_) = &func_def.body[0] {
-                    return false;
+            let outer_class = fixer.class.replace(class_def);
+            let outer_test_case = mem::replace(&mut fixer.test_case, test_case);
+            let mut modified = false;
+            for nested_stmt in class_def.body.iter().rev() {
+                if (test_case || pytest_class || matches!(nested_stmt, Stmt::ClassDef(_)))
+                    && fix_stmt(fixer, nested_stmt)
+                {
+                    modified = true;
                 }
             }
+            fixer.class = outer_class;
+            fixer.test_case = outer_test_case;
+            modified
+        }
+        _ => false,
+    }
+}
 
-            let start_line = src[..stmt.start().to_usize()].lines().count() - 1;
-            let end_line = src[..func_def.body.last().unwrap().end().to_usize()]
-                .lines()
-                .count()
-                - 1;
+/// Fix a method of a test class: lifecycle methods for their name, declaration and super()
+/// call, anything else as a possible module fixture or pytest hook.
+fn fix_method(fixer: &mut Fixer, stmt: &Stmt, func_def: &StmtFunctionDef) -> bool {
+    let src = fixer.src;
+    let line_index = fixer.line_index;
+    let canonical = match func_def.name.as_str() {
+        "setUp" | "setup" | "Setup" => "setUp",
+        "tearDown" | "teardown" | "Teardown" => "tearDown",
+        name => match [
+            "setUpClass",
+            "tearDownClass",
+            "setUpTestData",
+            "asyncSetUp",
+            "asyncTearDown",
+        ]
+        .into_iter()
+        .find(|canonical| name == *canonical || is_miscased(name, canonical))
+        {
+            Some(canonical) => canonical,
+            None => return fix_hook_name(fixer, func_def),
+        },
+    };
+    if func_def.body.is_empty() {
+        return false;
+    }
 
-            if start_line >= lines.len() || end_line >= lines.len() {
-                return false;
-            }
+    // Don't add super() to methods that only have pass
+    if func_def.body.len() == 1 {
+        if let Stmt::Pass(_) = &func_def.body[0] {
+            return false;
+        }
+    }
 
-            let mut modified = false;
+    // Locate the `def` line through the name, since the statement itself starts at
+    // its first decorator
+    let def_line = line_index.line(func_def.name.start().to_usize());
+    let body_line = line_index.line(func_def.body[0].start().to_usize());
+    let end_line = line_index.line(func_def.body.last().unwrap().end().to_usize());
 
-            // Fix the method name if needed
-            if let Some(new_name) = correct_name {
-                let old_name = func_def.name.as_str();
-                if let Some(line) = lines.get_mut(start_line) {
-                    let old_def = format!("def {}(", old_name);
-                    if line.contains(&old_def) {
-                        *line = line.replace(&old_def, &format!("def {}(", new_name));
-                        modified = true;
-                    }
-                }
-            }
+    // One-line methods (`def setUp(self): ...`) can't be rewritten line by line
+    if body_line <= def_line || end_line >= fixer.lines.len() {
+        return false;
+    }
+    let start_line = body_line - 1;
+
+    let mut modified = false;
+    let correct_call = format!("super().{}()", canonical);
+
+    // Legacy `super(Cls, self).setUp()` / `Base.setUp(self)` calls already chain to the
+    // parent. They are rewritten to `super().setUp()` on request where that reaches the
+    // same method, and otherwise kept as the call that has to come last.
+    let mut legacy_call = None;
+    for body_stmt in &func_def.body {
+        let Some(call) = super_call(body_stmt, canonical) else {
+            continue;
+        };
+        if matches!(call.form, SuperForm::ZeroArg) {
+            continue;
+        }
+        let text = &src[call.call.range()];
+        let call_line = line_index.line(call.call.start().to_usize());
+        let modernize = fixer.config.is_enabled(Rule::LegacySuper)
+            && fixer
+                .class
+                .is_some_and(|class_def| call.form.is_modernizable(class_def, src))
+            && call_line == line_index.line(call.call.end().to_usize());
+        if modernize {
+            fixer.lines[call_line] = fixer.lines[call_line].replacen(text, &correct_call, 1);
+            modified = true;
+        } else {
+            legacy_call = Some(text);
+        }
+    }
+    let correct_call = legacy_call.map_or(correct_call, str::to_string);
+
+    // Coroutine hooks have to await the parent's coroutine for it to run
+    let call_line = if is_async_hook(canonical) {
+        format!("await {}", correct_call)
+    } else {
+        correct_call.clone()
+    };
+    let lines = &mut fixer.lines;
 
-            // Remove super() calls from anywhere in the method body (they'll be added at the end)
-            let mut body_lines: Vec<String> = lines[start_line + 1..=end_line]
-                .iter()
-                .filter(|l| !l.contains("super().setUp()") && !l.contains("super().tearDown()"))
-                .map(|s| s.to_string())
-                .collect();
-
-            // Check if super() was on the last line
-            let last_line = &lines[end_line];
-            let had_super_at_end =
-                last_line.contains("super().setUp()") || last_line.contains("super().tearDown()");
-
-            // Add super() call if it wasn't already there or if it wasn't at the end
-            if body_lines.len() < (end_line - start_line) || !had_super_at_end {
-                // Get proper indentation from body
-                let body_indent = if !body_lines.is_empty() {
-                    body_lines[0]
-                        .chars()
-                        .take_while(|c| c.is_whitespace())
-                        .collect::<String>()
+    // Fix the method name (and declare async hooks with `async def`) if needed
+    let make_async = is_async_hook(canonical)
+        && !func_def.is_async
+        && fixer.config.is_enabled(Rule::AsyncHookNotCoroutine);
+    let rename = func_def.name.as_str() != canonical && fixer.config.is_enabled(Rule::MethodCasing);
+    if rename || make_async {
+        if let Some(line) = lines.get_mut(def_line) {
+            let old_def = format!("def {}(", func_def.name);
+            let new_def = format!(
+                "{}def {}(",
+                if make_async { "async " } else { "" },
+                if rename {
+                    canonical
                 } else {
-                    // Fallback: add 4 spaces
-                    lines[start_line]
-                        .chars()
-                        .take_while(|c| c.is_whitespace())
-                        .collect::<String>()
-                        + "    "
-                };
-
-                body_lines.push(format!("{}{}", body_indent, correct_call));
+                    func_def.name.as_str()
+                }
+            );
+            if line.contains(&old_def) {
+                *line = line.replace(&old_def, &new_def);
                 modified = true;
             }
+        }
+    }
 
-            if modified {
-                lines.splice(start_line + 1..=end_line, body_lines);
-            }
+    // Check if super() is already where the configured position wants it
+    let position = fixer.config.super_position(canonical);
+    let first_stmt = func_def.body.iter().find(|stmt| !is_docstring(stmt));
+    let analysis = flow::analyze(&func_def.body, canonical, src, line_index);
+    let in_place = match position {
+        SuperPosition::First => first_stmt.is_some_and(|first| {
+            lines[line_index.line(first.start().to_usize())].contains(&call_line)
+        }),
+        SuperPosition::Last | SuperPosition::Anywhere => {
+            analysis.violation(position).is_none()
+                && analysis
+                    .calls
+                    .iter()
+                    .all(|call| call.awaited || !is_async_hook(canonical))
+        }
+    };
 
-            modified
+    // Calls inside branches can't be moved line by line without emptying the branch or
+    // calling the parent twice, so those methods are left for a human
+    let top_level_calls = func_def
+        .body
+        .iter()
+        .filter(|stmt| super_call(stmt, canonical).is_some())
+        .count();
+    let nested_calls = analysis.calls.len() > top_level_calls;
+
+    // Opt-in: wrap cleanup that can raise, so the parent's teardown still runs
+    let wrap = fixer.config.is_enabled(Rule::FragileTeardown)
+        && teardown::is_teardown(canonical)
+        && position != SuperPosition::First
+        && !nested_calls
+        && teardown::unprotected_cleanup(&func_def.body, canonical).is_some();
+
+    // Every path already calls super() where the flow-checked positions want it, or the
+    // only call is already the first statement. The rewrite both moves the call and awaits
+    // it, so either rule enables it
+    let satisfied =
+        in_place && (position != SuperPosition::First || (top_level_calls == 1 && !nested_calls));
+    let rewrite = fixer.config.is_enabled(Rule::SuperPosition)
+        || fixer.config.is_enabled(Rule::UnawaitedSuper);
+    // A call moved to the end would be skipped by paths returning before it
+    let movable = position == SuperPosition::First || !analysis.returns;
+    if wrap || (!satisfied && !nested_calls && rewrite && movable) {
+        // Remove super() calls from anywhere in the method body (they'll be re-added
+        // where the position requires), remembering where the docstring ends
+        let lead = first_stmt.map_or(end_line - start_line, |first| {
+            line_index.line(first.start().to_usize()) - start_line - 1
+        });
+        let mut body_lines = Vec::new();
+        let mut insert_at = 0;
+        for (offset, line) in lines[start_line + 1..=end_line].iter().enumerate() {
+            if line.contains(&correct_call) {
+                continue;
+            }
+            if offset < lead {
+                insert_at += 1;
+            }
+            body_lines.push(line.clone());
         }
-        Stmt::ClassDef(class_def) => {
-            let mut modified = false;
-            for nested_stmt in &class_def.body {
-                if fix_stmt(nested_stmt, src, lines) {
-                    modified = true;
+
+        // Get proper indentation from body
+        let body_indent = if !body_lines.is_empty() {
+            body_lines[0]
+                .chars()
+                .take_while(|c| c.is_whitespace())
+                .collect::<String>()
+        } else {
+            // Fallback: add 4 spaces
+            lines[def_line]
+                .chars()
+                .take_while(|c| c.is_whitespace())
+                .collect::<String>()
+                + "    "
+        };
+
+        if wrap {
+            // The docstring stays in front; everything else goes into the `try:`
+            let mut wrapped: Vec<String> = body_lines.drain(..insert_at).collect();
+            wrapped.push(format!("{}try:", body_indent));
+            wrapped.extend(body_lines.into_iter().map(|line| {
+                if line.trim().is_empty() {
+                    line
+                } else {
+                    format!("    {}", line)
                 }
+            }));
+            wrapped.push(format!("{}finally:", body_indent));
+            wrapped.push(format!("{}    {}", body_indent, call_line));
+            lines.splice(start_line + 1..=end_line, wrapped);
+            modified = true;
+        } else if body_lines.len() < (end_line - start_line) || !in_place {
+            // Add super() call if it wasn't already there or if it wasn't in place
+            let call = format!("{}{}", body_indent, call_line);
+            match position {
+                SuperPosition::First => body_lines.insert(insert_at, call),
+                SuperPosition::Last | SuperPosition::Anywhere => body_lines.push(call),
+            }
+            // Putting the call back where it was isn't a change
+            if body_lines[..] != lines[start_line + 1..=end_line] {
+                lines.splice(start_line + 1..=end_line, body_lines);
+                modified = true;
             }
-            modified
         }
-        _ => false,
     }
+
+    // Class-level fixtures must be classmethods; the decorator goes above any others
+    if is_class_fixture(canonical)
+        && !is_classmethod(func_def)
+        && fixer.config.is_enabled(Rule::MissingClassmethod)
+    {
+        let first_line = line_index.line(stmt.start().to_usize());
+        let indent = lines[first_line]
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .collect::<String>();
+        lines.insert(first_line, format!("{}@classmethod", indent));
+        modified = true;
+    }
+
+    modified
+}
+
+/// Rename a misspelled hook that doesn't chain through super().
+///
+/// - In unittest modules: setupModule / setup_module -> setUpModule (and tearDownModule)
+/// - pytest xunit hooks: setupMethod -> setup_method, teardownModule -> teardown_module, ...
+fn fix_hook_name(fixer: &mut Fixer, func_def: &StmtFunctionDef) -> bool {
+    let name = func_def.name.as_str();
+    let canonical = match module_fixture_misspelling(name) {
+        Some(canonical) if fixer.unittest_module => {
+            Some(canonical).filter(|_| fixer.config.is_enabled(Rule::MethodCasing))
+        }
+        _ => xunit::near_miss(name).filter(|_| fixer.config.is_enabled(Rule::XunitSpelling)),
+    };
+    let Some(canonical) = canonical else {
+        return false;
+    };
+
+    let def_line = fixer.line_index.line(func_def.name.start().to_usize());
+    let Some(line) = fixer.lines.get_mut(def_line) else {
+        return false;
+    };
+
+    let old_def = format!("def {}(", name);
+    if !line.contains(&old_def) {
+        return false;
+    }
+    *line = line.replace(&old_def, &format!("def {}(", canonical));
+    true
 }
@@ -0,0 +1,481 @@
+// src/flow.rs
+//! Control-flow analysis of lifecycle methods.
+//!
+//! Follows every path through a method body to find out whether each normal exit (a `return`
+//! or falling off the end) has called the parent's method, and whether that call was the last
+//! thing it did. Paths that end in `raise` or `self.skipTest(...)` abort the test anyway and
+//! are exempt.
+//!
+//! Instead of enumerating every path, which grows exponentially with sequential branches, the
+//! analysis keeps one representative path per [`Called`] state at each point.
+
+use crate::{line_index::LineIndex, snippet, super_call, SuperCall, SuperPosition};
+use ruff_python_ast::{
+    ElifElseClause, ExceptHandler, Expr, MatchCase, Pattern, PatternMatchAs, Stmt,
+};
+use ruff_text_size::{Ranged, TextRange};
+
+/// Whether a path has called the parent's method so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Called {
+    /// Not yet
+    No,
+    /// The call was the last statement run
+    Last,
+    /// The call ran, but other statements followed it
+    Earlier,
+}
+
+impl Called {
+    const ALL: [Called; 3] = [Called::No, Called::Last, Called::Earlier];
+
+    fn index(self) -> usize {
+        match self {
+            Called::No => 0,
+            Called::Last => 1,
+            Called::Earlier => 2,
+        }
+    }
+}
+
+/// A representative path through the method.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Path {
+    /// The steps (branch decisions) taken
+    pub(crate) steps: Vec<String>,
+    /// The statement that breaks the position requirement on the path, if it was a single
+    /// statement: the first one run after the call, or a `return` before it
+    pub(crate) culprit: Option<TextRange>,
+}
+
+/// The paths reaching a point in the method: one representative per state.
+#[derive(Debug, Clone, Default)]
+struct Paths {
+    states: [Option<Path>; 3],
+}
+
+impl Paths {
+    /// The single path entering the method.
+    fn entry() -> Self {
+        let mut paths = Paths::default();
+        paths.states[Called::No.index()] = Some(Path::default());
+        paths
+    }
+
+    fn is_empty(&self) -> bool {
+        self.states.iter().all(Option::is_none)
+    }
+
+    fn get(&self, called: Called) -> Option<&Path> {
+        self.states[called.index()].as_ref()
+    }
+
+    /// Add `other`'s paths, keeping the existing representative where both have one.
+    fn merge(&mut self, other: Paths) {
+        for (state, other_state) in self.states.iter_mut().zip(other.states) {
+            if state.is_none() {
+                *state = other_state;
+            }
+        }
+    }
+
+    /// Record a step on every path that hasn't broken the position requirement yet; the steps
+    /// after its culprit don't explain anything.
+    fn with_step(mut self, step: impl FnOnce() -> String) -> Self {
+        let mut paths = self
+            .states
+            .iter_mut()
+            .flatten()
+            .filter(|path| path.culprit.is_none())
+            .peekable();
+        if paths.peek().is_some() {
+            let step = step();
+            for path in paths {
+                path.steps.push(step.clone());
+            }
+        }
+        self
+    }
+
+    /// Blame `stmt` for breaking the position requirement on the path in state `called`.
+    fn blame(mut self, called: Called, stmt: &Stmt) -> Self {
+        if let Some(path) = &mut self.states[called.index()] {
+            path.culprit = Some(stmt.range());
+        }
+        self
+    }
+
+    /// Move every path past `stmt`, which runs code other than the call: evaluating a
+    /// condition counts too, even when no branch is taken.
+    fn after(self, stmt: &Stmt) -> Self {
+        self.blame(Called::Last, stmt).map(after_statement)
+    }
+
+    /// Move every path to the state `f` maps its current state to.
+    fn map(self, f: impl Fn(Called) -> Called) -> Self {
+        let mut mapped = Paths::default();
+        for (called, state) in Called::ALL.into_iter().zip(self.states) {
+            let target = &mut mapped.states[f(called).index()];
+            if target.is_none() {
+                *target = state;
+            }
+        }
+        mapped
+    }
+}
+
+/// The outcome of following every path through a lifecycle method.
+#[derive(Debug)]
+pub(crate) struct Analysis<'a> {
+    /// A normal exit path that never calls the parent's method, if any
+    pub(crate) uncalled: Option<Path>,
+    /// A normal exit path that runs more code after calling it, if any
+    pub(crate) called_earlier: Option<Path>,
+    /// The calls to the parent's method found along the way
+    pub(crate) calls: Vec<SuperCall<'a>>,
+    /// Whether any path leaves through a `return` rather than the end of the method
+    pub(crate) returns: bool,
+}
+
+impl Analysis<'_> {
+    /// Return a normal exit path that breaks `position`, with what goes wrong on it.
+    ///
+    /// `last` needs the call to end every path, `anywhere-unconditional` only needs every path
+    /// to make it. `first` is a purely syntactic requirement, so no path ever breaks it here.
+    pub(crate) fn violation(&self, position: SuperPosition) -> Option<(&Path, &'static str)> {
+        let uncalled = self.uncalled.as_ref().map(|path| (path, "it is skipped"));
+        match position {
+            SuperPosition::First => None,
+            SuperPosition::Last => uncalled.or_else(|| {
+                self.called_earlier
+                    .as_ref()
+                    .map(|path| (path, "more code runs after it"))
+            }),
+            SuperPosition::Anywhere => uncalled,
+        }
+    }
+}
+
+/// Follow every path through `body`, tracking calls to the parent's `expected` method.
+///
+/// # Arguments
+/// * `body` - The lifecycle method's body
+/// * `expected` - The expected method name (e.g. "setUp" or "tearDownClass")
+/// * `src` - The file's source, used to describe branch conditions
+/// * `line_index` - Where the lines of `src` start
+pub(crate) fn analyze<'a>(
+    body: &'a [Stmt],
+    expected: &str,
+    src: &str,
+    line_index: &LineIndex,
+) -> Analysis<'a> {
+    let mut flow = Flow {
+        src,
+        line_index,
+        expected,
+        exits: Paths::default(),
+        loops: Vec::new(),
+        calls: Vec::new(),
+        returns: false,
+    };
+    let fall_through = flow.visit_body(body, Paths::entry());
+    flow.exits.merge(fall_through);
+
+    Analysis {
+        uncalled: flow.exits.get(Called::No).cloned(),
+        called_earlier: flow.exits.get(Called::Earlier).cloned(),
+        calls: flow.calls,
+        returns: flow.returns,
+    }
+}
+
+/// The paths leaving a loop early.
+#[derive(Default)]
+struct LoopExits {
+    breaks: Paths,
+    continues: Paths,
+}
+
+struct Flow<'a, 's> {
+    src: &'s str,
+    line_index: &'s LineIndex,
+    expected: &'s str,
+    /// Paths that have left the method normally
+    exits: Paths,
+    /// The enclosing loops, innermost last
+    loops: Vec<LoopExits>,
+    calls: Vec<SuperCall<'a>>,
+    returns: bool,
+}
+
+impl<'a> Flow<'a, '_> {
+    /// Follow `paths` through `body`, returning the paths that reach its end.
+    fn visit_body(&mut self, body: &'a [Stmt], mut paths: Paths) -> Paths {
+        for stmt in body {
+            if paths.is_empty() {
+                // The rest of the block is unreachable
+                break;
+            }
+            paths = self.visit_stmt(stmt, paths);
+        }
+        paths
+    }
+
+    /// Follow `paths` through `stmt`, returning the paths that continue after it.
+    fn visit_stmt(&mut self, stmt: &'a Stmt, paths: Paths) -> Paths {
+        if let Some(call) = super_call(stmt, self.expected) {
+            self.calls.push(call);
+            return paths.map(|_| Called::Last);
+        }
+
+        match stmt {
+            // Paths that abort the test are exempt
+            Stmt::Raise(_) => Paths::default(),
+            Stmt::Expr(expr_stmt) if is_skip_test(&expr_stmt.value) => Paths::default(),
+            Stmt::Return(_) => {
+                // Like any other statement, a `return` after the call means it wasn't last
+                self.returns = true;
+                let line = self.line(stmt);
+                self.exits.merge(
+                    paths
+                        .with_step(|| format!("it returns at line {}", line))
+                        .blame(Called::No, stmt)
+                        .blame(Called::Last, stmt)
+                        .map(after_statement),
+                );
+                Paths::default()
+            }
+            Stmt::If(if_stmt) => {
+                let test = self.text(&*if_stmt.test);
+                let line = self.line(stmt);
+                let mut after = self.visit_body(
+                    &if_stmt.body,
+                    paths
+                        .clone()
+                        .with_step(|| format!("`if {}` is true (line {})", test, line)),
+                );
+
+                let mut has_else = false;
+                for clause in &if_stmt.elif_else_clauses {
+                    let ElifElseClause {
+                        test: clause_test,
+                        body,
+                        ..
+                    } = clause;
+                    let clause_line = self.line(clause);
+                    let taken = match clause_test {
+                        Some(clause_test) => {
+                            let clause_test = self.text(clause_test);
+                            paths.clone().with_step(|| {
+                                format!("`elif {}` is true (line {})", clause_test, clause_line)
+                            })
+                        }
+                        None => {
+                            has_else = true;
+                            paths.clone().with_step(|| {
+                                not_taken(&test, line, if_stmt.elif_else_clauses.len() > 1)
+                            })
+                        }
+                    };
+                    after.merge(self.visit_body(body, taken));
+                }
+
+                // Without an `else`, every condition can be false
+                if !has_else {
+                    after.merge(paths.after(stmt).with_step(|| {
+                        not_taken(&test, line, !if_stmt.elif_else_clauses.is_empty())
+                    }));
+                }
+                after
+            }
+            Stmt::For(for_stmt) => {
+                self.visit_loop("for", stmt, &for_stmt.body, &for_stmt.orelse, paths, false)
+            }
+            Stmt::While(while_stmt) => {
+                // `while True:` only ever leaves through `break`
+                let infinite =
+                    matches!(&*while_stmt.test, Expr::BooleanLiteral(literal) if literal.value);
+                self.visit_loop(
+                    "while",
+                    stmt,
+                    &while_stmt.body,
+                    &while_stmt.orelse,
+                    paths,
+                    infinite,
+                )
+            }
+            Stmt::With(with_stmt) => self.visit_body(&with_stmt.body, paths),
+            Stmt::Try(try_stmt) => {
+                // Returns inside the `try` still run the `finally` block, so collect them
+                // separately
+                let outer_exits = std::mem::take(&mut self.exits);
+
+                // Any statement of the `try` can raise, so the handlers are reached by the paths
+                // entering each of them. One raised by the parent's method means it ran
+                let mut raised = Paths::default();
+                let mut body_end = paths;
+                for stmt in &try_stmt.body {
+                    if body_end.is_empty() {
+                        break;
+                    }
+                    let next = self.visit_stmt(stmt, body_end.clone());
+                    if super_call(stmt, self.expected).is_some() {
+                        raised.merge(next.clone());
+                    } else {
+                        raised.merge(body_end);
+                    }
+                    body_end = next;
+                }
+                let mut after = self.visit_body(&try_stmt.orelse, body_end);
+
+                for handler in &try_stmt.handlers {
+                    let ExceptHandler::ExceptHandler(handler) = handler;
+                    let line = self.line(handler);
+                    let caught = match &handler.type_ {
+                        Some(type_) => format!("`except {}`", self.text(&**type_)),
+                        None => "a bare `except`".to_string(),
+                    };
+                    let handled = raised
+                        .clone()
+                        .with_step(|| format!("{} catches an exception (line {})", caught, line));
+                    after.merge(self.visit_body(&handler.body, handled));
+                }
+
+                let inner_exits = std::mem::replace(&mut self.exits, outer_exits);
+                if try_stmt.finalbody.is_empty() {
+                    self.exits.merge(inner_exits);
+                    after
+                } else {
+                    let returned = self.visit_body(&try_stmt.finalbody, inner_exits);
+                    self.exits.merge(returned);
+                    self.visit_body(&try_stmt.finalbody, after)
+                }
+            }
+            Stmt::Match(match_stmt) => {
+                let mut after = Paths::default();
+                let mut exhaustive = false;
+                for case in &match_stmt.cases {
+                    let MatchCase {
+                        pattern,
+                        guard,
+                        body,
+                        ..
+                    } = case;
+                    let pattern_text = self.text(pattern);
+                    let line = self.line(case);
+                    let matched = paths
+                        .clone()
+                        .with_step(|| format!("`case {}` matches (line {})", pattern_text, line));
+                    after.merge(self.visit_body(body, matched));
+
+                    // A bare capture or `_` without a guard matches anything
+                    if guard.is_none()
+                        && matches!(
+                            pattern,
+                            Pattern::MatchAs(PatternMatchAs { pattern: None, .. })
+                        )
+                    {
+                        exhaustive = true;
+                        break;
+                    }
+                }
+                if !exhaustive {
+                    let subject = self.text(&*match_stmt.subject);
+                    let line = self.line(stmt);
+                    after.merge(paths.after(stmt).with_step(|| {
+                        format!("no case of `match {}` matches (line {})", subject, line)
+                    }));
+                }
+                after
+            }
+            Stmt::Break(_) => {
+                let line = self.line(stmt);
+                if let Some(exits) = self.loops.last_mut() {
+                    exits
+                        .breaks
+                        .merge(paths.with_step(|| format!("it breaks out at line {}", line)));
+                }
+                Paths::default()
+            }
+            Stmt::Continue(_) => {
+                if let Some(exits) = self.loops.last_mut() {
+                    exits.continues.merge(paths);
+                }
+                Paths::default()
+            }
+            _ => paths.after(stmt),
+        }
+    }
+
+    /// Follow `paths` through a `for` or `while` loop and its `else` block.
+    ///
+    /// The body may run zero or more times; after a `break` the `else` block is skipped.
+    fn visit_loop(
+        &mut self,
+        keyword: &str,
+        stmt: &Stmt,
+        body: &'a [Stmt],
+        orelse: &'a [Stmt],
+        paths: Paths,
+        infinite: bool,
+    ) -> Paths {
+        self.loops.push(LoopExits::default());
+        let body_end = self.visit_body(body, paths.clone());
+        let LoopExits { breaks, continues } = self.loops.pop().unwrap_or_default();
+
+        let mut finished = Paths::default();
+        if !infinite {
+            let line = self.line(stmt);
+            finished.merge(body_end);
+            finished.merge(continues);
+            finished.merge(
+                paths.after(stmt).with_step(|| {
+                    format!("the `{}` loop runs zero times (line {})", keyword, line)
+                }),
+            );
+        }
+
+        let mut after = self.visit_body(orelse, finished);
+        after.merge(breaks);
+        after
+    }
+
+    /// Return the one-based line `node` starts on.
+    fn line(&self, node: impl Ranged) -> usize {
+        self.line_index.line_number(node)
+    }
+
+    /// Return the source text of `node`, shortened to its first line.
+    fn text(&self, node: impl Ranged) -> String {
+        snippet(self.src, node)
+    }
+}
+
+/// Return the state after running a statement other than the call.
+fn after_statement(called: Called) -> Called {
+    match called {
+        Called::No => Called::No,
+        Called::Last | Called::Earlier => Called::Earlier,
+    }
+}
+
+/// Describe an `if` statement where no branch was taken.
+fn not_taken(test: &str, line: usize, has_elif: bool) -> String {
+    if has_elif {
+        format!("no branch of `if {}` is taken (line {})", test, line)
+    } else {
+        format!("`if {}` is false (line {})", test, line)
+    }
+}
+
+/// Check if an expression is a `self.skipTest(...)` call.
+fn is_skip_test(expr: &Expr) -> bool {
+    let Expr::Call(call) = expr else {
+        return false;
+    };
+    let Expr::Attribute(attr) = &*call.func else {
+        return false;
+    };
+    attr.attr.as_str() == "skipTest"
+        && matches!(&*attr.value, Expr::Name(name) if name.id.as_str() == "self")
+}
@@ -0,0 +1,201 @@
+// src/hierarchy.rs
+//! The inherited chain rules: ancestors and mixins that keep lifecycle methods from running.
+//!
+//! When a shared base class's `setUp` skips `super().setUp()`, every test class deriving from
+//! it loses the setup of the classes after it, yet the per-method check only reports it once,
+//! in the base's file. These rules follow each leaf test class's (one no project class derives
+//! from) method resolution order and report on the leaf:
+//!
+//! - an ancestor or mixin whose method ends the chain before later ones, such as the TestCase,
+//!   get to run, with the classes leading to it;
+//! - mixin methods the MRO puts after the TestCase, whose own method never calls super(), so
+//!   that they never run (`class T(TestCase, DbMixin)`).
+
+use crate::{
+    classes::{is_unknown_root, is_unresolved_root},
+    flow,
+    index::{ClassRef, ProjectIndex},
+    is_async_hook,
+    mro::{self, MroEntry},
+    Checker, Rule,
+};
+use ruff_python_ast::{Stmt, StmtClassDef, StmtFunctionDef};
+
+/// The lifecycle methods that chain to their parent's through super(), and so the ones
+/// `--super-position` can configure
+pub const CHAINED_METHODS: &[&str] = &[
+    "setUp",
+    "tearDown",
+    "setUpClass",
+    "tearDownClass",
+    "setUpTestData",
+    "asyncSetUp",
+    "asyncTearDown",
+];
+
+/// Report the classes in a leaf test class's MRO that keep lifecycle methods from running.
+pub(crate) fn check_inherited_chains<'a>(
+    checker: &mut Checker<'a>,
+    stmt: &Stmt,
+    class: ClassRef<'a>,
+) {
+    let index = checker.index;
+    if index.is_subclassed(class) {
+        return;
+    }
+    let Some(mro) = mro::linearize(index, class) else {
+        return;
+    };
+    let roots = &checker.config.test_case_roots;
+
+    let outer_method = checker.method;
+    for &method in CHAINED_METHODS {
+        checker.method = Some(method);
+        // Follow super() calls down the MRO to the method that doesn't make one. TestCase
+        // classes end the chains of the methods they define: unittest's own hooks don't call
+        // super()
+        let Some(end) = mro.iter().position(|entry| match entry {
+            MroEntry::Class(class) => find_method(class.class_def, method)
+                .is_some_and(|func_def| !calls_super(index, class.file, func_def, method)),
+            MroEntry::Imported(name) => {
+                is_unresolved_root(name, roots) && root_defines(name, method)
+            }
+            MroEntry::Unknown(name) => is_unknown_root(name, roots) && root_defines(name, method),
+        }) else {
+            continue;
+        };
+        // If the leaf's own method ends the chain, that is the per-method check's to report
+        if end == 0 {
+            continue;
+        }
+
+        let chain: Vec<&str> = mro[..=end].iter().map(MroEntry::name).collect();
+        let call = format!(
+            "{}super().{}()",
+            if is_async_hook(method) { "await " } else { "" },
+            method
+        );
+        let skipped: Vec<&MroEntry> = mro[end + 1..]
+            .iter()
+            .filter(|entry| match entry {
+                MroEntry::Class(class) => find_method(class.class_def, method).is_some(),
+                MroEntry::Imported(name) => {
+                    is_unresolved_root(name, roots) && root_defines(name, method)
+                }
+                MroEntry::Unknown(name) => {
+                    is_unknown_root(name, roots) && root_defines(name, method)
+                }
+            })
+            .collect();
+
+        match &mro[end] {
+            MroEntry::Class(broken) => {
+                if skipped.is_empty() {
+                    continue;
+                }
+                let func_def = find_method(broken.class_def, method).unwrap();
+                let source = &index.files[broken.file];
+                let skipped: Vec<String> = skipped
+                    .iter()
+                    .map(|entry| format!("{}.{}", entry.name(), method))
+                    .collect();
+                checker.report(
+                    Rule::BrokenInheritedChain,
+                    stmt,
+                    format_args!(
+                        "inherited {} chain is broken: {}.{} ({}:{}) never calls {}, so {} never {}",
+                        method,
+                        chain.join(" -> "),
+                        method,
+                        source.path.display(),
+                        source.line_index.line_number(func_def),
+                        call,
+                        join_and(&skipped),
+                        if skipped.len() == 1 { "runs" } else { "run" }
+                    ),
+                );
+            }
+            // A mixin after the TestCase in the MRO is never reached
+            root => {
+                for entry in skipped {
+                    let MroEntry::Class(mixin) = entry else {
+                        continue;
+                    };
+                    let func_def = find_method(mixin.class_def, method).unwrap();
+                    let source = &index.files[mixin.file];
+                    checker.report(
+                        Rule::UnreachableMixinHook,
+                        stmt,
+                        format_args!(
+                            "{}.{} ({}:{}) is unreachable: the MRO {} -> {} reaches {}.{} first, which never calls {}; list {} before {} in the bases",
+                            mixin.class_def.name,
+                            method,
+                            source.path.display(),
+                            source.line_index.line_number(func_def),
+                            chain.join(" -> "),
+                            mixin.class_def.name,
+                            root.name(),
+                            method,
+                            call,
+                            mixin.class_def.name,
+                            root.name()
+                        ),
+                    );
+                }
+            }
+        }
+    }
+    checker.method = outer_method;
+}
+
+/// Check if the TestCase class `root`, a qualified or bare name, defines `method`.
+///
+/// Every TestCase has `setUp` / `tearDown` and the class fixtures, but only
+/// `IsolatedAsyncioTestCase` has the async hooks and only Django's `TestCase` has
+/// `setUpTestData`. Project roots may have either, so they're taken not to.
+fn root_defines(root: &str, method: &str) -> bool {
+    let class = root.rsplit('.').next().unwrap_or(root);
+    match method {
+        "asyncSetUp" | "asyncTearDown" => {
+            class == "IsolatedAsyncioTestCase" && (root == class || root.starts_with("unittest."))
+        }
+        "setUpTestData" => matches!(
+            root,
+            "django.test.TestCase" | "django.test.testcases.TestCase"
+        ),
+        _ => true,
+    }
+}
+
+/// Join names as "A, B and C".
+fn join_and(names: &[String]) -> String {
+    match names {
+        [] => String::new(),
+        [name] => name.clone(),
+        [init @ .., last] => format!("{} and {}", init.join(", "), last),
+    }
+}
+
+/// Return the method `name` a class body defines; a later definition replaces an earlier one.
+pub(crate) fn find_method<'a>(
+    class_def: &'a StmtClassDef,
+    name: &str,
+) -> Option<&'a StmtFunctionDef> {
+    class_def.body.iter().rev().find_map(|stmt| match stmt {
+        Stmt::FunctionDef(func_def) if func_def.name.as_str() == name => Some(func_def),
+        _ => None,
+    })
+}
+
+/// Check if a method calls super() to itself anywhere, in any form.
+pub(crate) fn calls_super(
+    index: &ProjectIndex,
+    file: usize,
+    func_def: &StmtFunctionDef,
+    method: &str,
+) -> bool {
+    let source = &index.files[file];
+    !flow::analyze(&func_def.body, method, &source.src, &source.line_index)
+        .calls
+        .is_empty()
+}
@@ -0,0 +1,365 @@
+// src/index.rs
+//! The project index: the files being checked and the project files they import, parsed once.
+//!
+//! Test classes usually inherit from a base class in another file, such as
+//! `tests/base.py::ApiTestCase`, so whether a class is a test class can't be told from its own
+//! file. [`Project::load`] parses the given files and follows their imports into the project's
+//! own modules; [`ProjectIndex`] then resolves class bases across those files, through relative
+//! imports and the names a package's `__init__.py` re-exports.
+//!
+//! Absolute imports are looked up the way pytest's default import mode finds them: from the
+//! current directory, and from the first ancestor of each given file that isn't a package.
+
+use crate::classes::{is_unknown_root, is_unresolved_root, Base, ModuleScope};
+use crate::line_index::LineIndex;
+use ruff_python_ast::{ModModule, PySourceType, StmtClassDef};
+use ruff_python_parser::{parse_unchecked_source, ParseError};
+use std::{
+    collections::{HashMap, HashSet},
+    env, fs, io,
+    path::{Path, PathBuf},
+    ptr,
+};
+
+/// How many re-exports to follow for one name, in case they form a cycle
+const MAX_REEXPORTS: usize = 16;
+
+/// A parsed Python file.
+pub(crate) struct SourceFile {
+    /// The path, as given or as found for an import
+    pub(crate) path: PathBuf,
+    pub(crate) src: String,
+    /// Where the lines of `src` start
+    pub(crate) line_index: LineIndex,
+    /// The module, as far as the parser could recover it from any syntax errors
+    pub(crate) module: ModModule,
+    /// Why the file couldn't be read or parsed cleanly, if it couldn't
+    pub(crate) errors: Vec<LoadError>,
+    /// The project files of the modules it imports, by module name as written (`.base`)
+    imports: HashMap<String, usize>,
+}
+
+/// A problem reading or parsing a file.
+pub(crate) enum LoadError {
+    /// The file couldn't be read; it is checked as if it were empty
+    Io(io::Error),
+    /// The file isn't valid UTF-8 from this byte offset on; it is checked with the
+    /// undecodable bytes replaced
+    Decode(usize),
+    /// A syntax error the parser recovered from
+    Syntax(ParseError),
+}
+
+/// The given files, followed by the project files they import.
+pub(crate) struct Project {
+    pub(crate) files: Vec<SourceFile>,
+    /// How many of `files` were given, rather than found through an import; given files that
+    /// can't be read or parsed are kept, with their errors
+    pub(crate) given: usize,
+}
+
+impl Project {
+    /// Parse the files at `paths` and, transitively, the project files they import.
+    pub(crate) fn load(paths: &[&Path]) -> Self {
+        let cwd = env::current_dir().ok();
+        let mut roots: Vec<PathBuf> = cwd.iter().cloned().collect();
+        for root in paths.iter().filter_map(|path| import_root(path)) {
+            if !roots.contains(&root) {
+                roots.push(root);
+            }
+        }
+
+        let mut project = Project {
+            files: Vec::new(),
+            given: 0,
+        };
+        let mut by_path = HashMap::new();
+        for path in paths {
+            project.add(path.to_path_buf(), &mut by_path, true);
+        }
+        project.given = project.files.len();
+
+        // Files found while following imports are appended, and followed in turn
+        let mut file = 0;
+        while file < project.files.len() {
+            let modules = ModuleScope::new(file, &project.files[file].module.body)
+                .modules()
+                .to_vec();
+            for name in modules {
+                let Some(path) = module_path(&project.files[file].path, &name, &roots) else {
+                    continue;
+                };
+                // Report paths of found files relative to the current directory, as given ones are
+                let path = match &cwd {
+                    Some(cwd) => path
+                        .strip_prefix(cwd)
+                        .map_or(path.clone(), Path::to_path_buf),
+                    None => path,
+                };
+                if let Some(imported) = project.add(path, &mut by_path, false) {
+                    project.files[file].imports.insert(name, imported);
+                }
+            }
+            file += 1;
+        }
+
+        project
+    }
+
+    /// Parse the file at `path` unless it is already loaded, and return its index.
+    ///
+    /// Imported files that can't be read are skipped, but `given` ones are loaded as empty, so
+    /// that the error can be reported.
+    fn add(
+        &mut self,
+        path: PathBuf,
+        by_path: &mut HashMap<PathBuf, usize>,
+        given: bool,
+    ) -> Option<usize> {
+        let key = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+        if let Some(&file) = by_path.get(&key) {
+            return Some(file);
+        }
+
+        let mut errors = Vec::new();
+        let src = match fs::read(&path) {
+            Ok(bytes) => String::from_utf8(bytes).unwrap_or_else(|err| {
+                errors.push(LoadError::Decode(err.utf8_error().valid_up_to()));
+                String::from_utf8_lossy(err.as_bytes()).into_owned()
+            }),
+            Err(err) if given => {
+                errors.push(LoadError::Io(err));
+                String::new()
+            }
+            Err(_) => return None,
+        };
+        // The parser recovers from syntax errors, so the rest of the file is still checked
+        let parsed = parse_unchecked_source(&src, PySourceType::Python);
+        errors.extend(parsed.errors().iter().cloned().map(LoadError::Syntax));
+        self.files.push(SourceFile {
+            path,
+            line_index: LineIndex::new(&src),
+            src,
+            module: parsed.into_syntax(),
+            errors,
+            imports: HashMap::new(),
+        });
+        by_path.insert(key, self.files.len() - 1);
+        Some(self.files.len() - 1)
+    }
+}
+
+/// Return the directory pytest imports the file at `path` from: the first ancestor that isn't
+/// a package.
+fn import_root(path: &Path) -> Option<PathBuf> {
+    let mut dir = fs::canonicalize(path).ok()?.parent()?.to_path_buf();
+    while dir.join("__init__.py").is_file() {
+        dir = dir.parent()?.to_path_buf();
+    }
+    Some(dir)
+}
+
+/// Find the file of the module `name` imported by the file at `importer`.
+///
+/// Relative names (`.base`, `..`) are found from the importer's package, absolute names from
+/// the import `roots`; a module is either `name.py` or a package's `name/__init__.py`.
+fn module_path(importer: &Path, name: &str, roots: &[PathBuf]) -> Option<PathBuf> {
+    let relative = name.trim_start_matches('.');
+    let level = name.len() - relative.len();
+    let parts: PathBuf = relative
+        .split('.')
+        .filter(|part| !part.is_empty())
+        .collect();
+
+    let bases = if level > 0 {
+        let importer = fs::canonicalize(importer).ok()?;
+        let mut dir = importer.parent()?;
+        for _ in 1..level {
+            dir = dir.parent()?;
+        }
+        vec![dir.to_path_buf()]
+    } else {
+        roots.to_vec()
+    };
+
+    bases
+        .into_iter()
+        .flat_map(|base| {
+            let module = base.join(&parts);
+            if relative.is_empty() {
+                // `from . import name`: the package itself
+                vec![module.join("__init__.py")]
+            } else {
+                vec![module.with_extension("py"), module.join("__init__.py")]
+            }
+        })
+        .find(|candidate| candidate.is_file())
+}
+
+/// A class defined in one of the project's files.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ClassRef<'a> {
+    /// The index of its file in the project
+    pub(crate) file: usize,
+    pub(crate) class_def: &'a StmtClassDef,
+}
+
+impl PartialEq for ClassRef<'_> {
+    /// Classes are the same definition, not equal-looking ones
+    fn eq(&self, other: &Self) -> bool {
+        self.file == other.file && ptr::eq(self.class_def, other.class_def)
+    }
+}
+
+impl Eq for ClassRef<'_> {}
+
+/// The imports and classes of every file in a [`Project`], for resolving classes across files.
+pub(crate) struct ProjectIndex<'a> {
+    pub(crate) files: &'a [SourceFile],
+    scopes: Vec<ModuleScope<'a>>,
+    /// The classes other project classes derive from, by file and name
+    subclassed: HashSet<(usize, &'a str)>,
+}
+
+impl<'a> ProjectIndex<'a> {
+    pub(crate) fn new(project: &'a Project) -> Self {
+        let mut index = ProjectIndex {
+            files: &project.files,
+            scopes: project
+                .files
+                .iter()
+                .enumerate()
+                .map(|(file, source)| ModuleScope::new(file, &source.module.body))
+                .collect(),
+            subclassed: HashSet::new(),
+        };
+
+        let subclassed = index
+            .scopes
+            .iter()
+            .flat_map(|scope| scope.classes())
+            .flat_map(|class| index.bases(class))
+            .filter_map(|base| match base {
+                Base::Class(base) => Some((base.file, base.class_def.name.as_str())),
+                _ => None,
+            })
+            .collect();
+        index.subclassed = subclassed;
+        index
+    }
+
+    /// Return the imports and classes of a file.
+    pub(crate) fn scope(&self, file: usize) -> &ModuleScope<'a> {
+        &self.scopes[file]
+    }
+
+    /// Resolve the bases of a class, following imported ones into the project's files.
+    pub(crate) fn bases(&self, class: ClassRef<'a>) -> Vec<Base<'a>> {
+        let scope = self.scope(class.file);
+        class
+            .class_def
+            .bases()
+            .iter()
+            .filter_map(|base| scope.resolve(base))
+            .map(|base| match base {
+                Base::Imported(qualified) => match self.resolve_import(class.file, &qualified) {
+                    Some(base) => Base::Class(base),
+                    None => Base::Imported(qualified),
+                },
+                base => base,
+            })
+            .collect()
+    }
+
+    /// Check if any class in the project derives from `class`.
+    pub(crate) fn is_subclassed(&self, class: ClassRef<'a>) -> bool {
+        self.subclassed
+            .contains(&(class.file, class.class_def.name.as_str()))
+    }
+
+    /// Find the project class that a file imports under a qualified name, such as
+    /// `tests.base.ApiTestCase` or `.base.ApiTestCase`.
+    ///
+    /// The longest prefix that names an imported project module is the module; if that module
+    /// only imports the class itself, as a package's `__init__.py` re-exporting it does, the
+    /// import is followed.
+    pub(crate) fn resolve_import(&self, file: usize, qualified: &str) -> Option<ClassRef<'a>> {
+        let mut file = file;
+        let mut qualified = qualified.to_string();
+        for _ in 0..MAX_REEXPORTS {
+            let (module, names) = self.split_module(file, &qualified)?;
+            let scope = self.scope(module);
+            let (name, rest) = names.split_first()?;
+            if let Some(class) = scope.class(name) {
+                return rest.is_empty().then_some(class);
+            }
+
+            let reexported = scope.import(name)?;
+            qualified = if rest.is_empty() {
+                reexported.to_string()
+            } else {
+                format!("{}.{}", reexported, rest.join("."))
+            };
+            file = module;
+        }
+        None
+    }
+
+    /// Split a qualified name into the project module it starts with, as imported by `file`,
+    /// and the names after it.
+    fn split_module<'q>(&self, file: usize, qualified: &'q str) -> Option<(usize, Vec<&'q str>)> {
+        let relative = qualified.trim_start_matches('.');
+        let dots = &qualified[..qualified.len() - relative.len()];
+        let parts: Vec<&str> = relative
+            .split('.')
+            .filter(|part| !part.is_empty())
+            .collect();
+
+        (0..parts.len()).rev().find_map(|split| {
+            if dots.is_empty() && split == 0 {
+                return None;
+            }
+            let module = format!("{}{}", dots, parts[..split].join("."));
+            let &module = self.files[file].imports.get(&module)?;
+            Some((module, parts[split..].to_vec()))
+        })
+    }
+
+    /// Check if a class derives from one of the TestCase `roots`.
+    pub(crate) fn is_test_class(&self, class: ClassRef<'a>, roots: &[String]) -> bool {
+        self.derives_from_root(class, roots, &mut HashSet::new())
+    }
+
+    fn derives_from_root(
+        &self,
+        class: ClassRef<'a>,
+        roots: &[String],
+        seen: &mut HashSet<(usize, &'a str)>,
+    ) -> bool {
+        // Guard against `class A(B)` / `class B(A)` cycles
+        if !seen.insert((class.file, class.class_def.name.as_str())) {
+            return false;
+        }
+
+        let scope = self.scope(class.file);
+        class
+            .class_def
+            .bases()
+            .iter()
+            .any(|base| match scope.resolve(base) {
+                Some(Base::Class(base)) => self.derives_from_root(base, roots, seen),
+                // A project class can be a root itself; otherwise its own bases decide
+                Some(Base::Imported(qualified)) => {
+                    if roots.contains(&qualified) {
+                        true
+                    } else if let Some(base) = self.resolve_import(class.file, &qualified) {
+                        self.derives_from_root(base, roots, seen)
+                    } else {
+                        is_unresolved_root(&qualified, roots)
+                    }
+                }
+                Some(Base::Unknown(name)) => is_unknown_root(&name, roots),
+                None => false,
+            })
+    }
+}
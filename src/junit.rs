@@ -0,0 +1,182 @@
+// src/junit.rs
+//! JUnit XML output, for CI systems that track results as tests.
+//!
+//! Each checked file is a `<testsuite>`, and each lifecycle method or hook checked in it a
+//! `<testcase>` named after the method, with the module and class as its `classname`. A
+//! method passes unless it has violations, which become its `<failure>`. Violations about a
+//! method that isn't defined in the file, such as an inherited setUp chain that a leaf class
+//! doesn't override, get a test case of their own, so every violation is reported.
+
+use crate::{output::Run, CheckedMethod, Diagnostic, Location};
+use std::{fmt::Write, path::Path};
+
+/// A `<testcase>`: a method, and the violations reported about it.
+struct TestCase<'a> {
+    class: Option<&'a str>,
+    method: Option<&'a str>,
+    start: Location,
+    diagnostics: Vec<&'a Diagnostic>,
+}
+
+/// Render the run as a JUnit XML report.
+pub(crate) fn report(run: &Run, methods: &[CheckedMethod]) -> String {
+    // Files with violations are reported even if they weren't given, e.g. by a library caller
+    let mut files: Vec<&Path> = run.files_checked.to_vec();
+    for file in methods
+        .iter()
+        .map(|method| method.file.as_path())
+        .chain(run.diagnostics.iter().map(|d| d.file.as_path()))
+    {
+        if !files.contains(&file) {
+            files.push(file);
+        }
+    }
+
+    let suites: Vec<(&Path, Vec<TestCase>)> = files
+        .into_iter()
+        .map(|file| (file, test_cases(file, run.diagnostics, methods)))
+        .collect();
+    let tests: usize = suites.iter().map(|(_, cases)| cases.len()).sum();
+    let failed: usize = suites.iter().map(|(_, cases)| failures(cases)).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\">",
+        env!("CARGO_PKG_NAME"),
+        tests,
+        failed
+    );
+    for (file, cases) in &suites {
+        let file = file.to_string_lossy();
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\">",
+            escape(&file),
+            cases.len(),
+            failures(cases)
+        );
+        for case in cases {
+            write_test_case(&mut xml, &file, case);
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Return the test cases of `file`: its checked methods, and the methods only diagnostics
+/// mention, in source order.
+fn test_cases<'a>(
+    file: &Path,
+    diagnostics: &'a [Diagnostic],
+    methods: &'a [CheckedMethod],
+) -> Vec<TestCase<'a>> {
+    let mut cases: Vec<TestCase> = methods
+        .iter()
+        .filter(|method| method.file == file)
+        .map(|method| TestCase {
+            class: method.class.as_deref(),
+            method: Some(&method.method),
+            start: method.start,
+            diagnostics: diagnostics
+                .iter()
+                .filter(|diagnostic| method.is_reported_by(diagnostic))
+                .collect(),
+        })
+        .collect();
+
+    for diagnostic in diagnostics.iter().filter(|d| d.file == file) {
+        if methods
+            .iter()
+            .any(|method| method.is_reported_by(diagnostic))
+        {
+            continue;
+        }
+        let class = diagnostic.class.as_deref();
+        let method = diagnostic.method.as_deref();
+        match cases
+            .iter_mut()
+            .find(|case| case.class == class && case.method == method)
+        {
+            Some(case) => case.diagnostics.push(diagnostic),
+            None => cases.push(TestCase {
+                class,
+                method,
+                start: diagnostic.start,
+                diagnostics: vec![diagnostic],
+            }),
+        }
+    }
+    cases.sort_by_key(|case| case.start);
+    cases
+}
+
+/// Return how many of `cases` fail.
+fn failures(cases: &[TestCase]) -> usize {
+    cases
+        .iter()
+        .filter(|case| !case.diagnostics.is_empty())
+        .count()
+}
+
+/// Write a `<testcase>`, with a `<failure>` listing its violations if it has any.
+fn write_test_case(xml: &mut String, file: &str, case: &TestCase) {
+    let module = file
+        .trim_start_matches("./")
+        .trim_end_matches(".py")
+        .replace(['/', '\\'], ".");
+    let classname = match case.class {
+        Some(class) => format!("{}.{}", module, class),
+        None => module,
+    };
+    let _ = write!(
+        xml,
+        "    <testcase name=\"{}\" classname=\"{}\" file=\"{}\" line=\"{}\"",
+        escape(case.method.unwrap_or("<module>")),
+        escape(&classname),
+        escape(file),
+        case.start.line
+    );
+    if case.diagnostics.is_empty() {
+        xml.push_str("/>\n");
+        return;
+    }
+
+    // JUnit allows one failure per test case, so it lists all of the method's violations
+    let types: Vec<&str> = case.diagnostics.iter().map(|d| d.rule.code()).collect();
+    let messages: Vec<&str> = case
+        .diagnostics
+        .iter()
+        .map(|d| d.message.as_str())
+        .collect();
+    let lines: Vec<String> = case.diagnostics.iter().map(|d| d.to_string()).collect();
+    let _ = write!(
+        xml,
+        ">\n      <failure type=\"{}\" message=\"{}\">{}</failure>\n    </testcase>\n",
+        escape(&types.join(",")),
+        escape(&messages.join("; ")),
+        escape(&lines.join("\n"))
+    );
+}
+
+/// Escape `text` for XML text and attribute values.
+///
+/// Control characters other than tab and newlines can't appear in XML 1.0 at all, even
+/// escaped, so they are replaced with U+FFFD.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            '\u{0}'..='\u{1f}' | '\u{fffe}' | '\u{ffff}' => escaped.push('\u{fffd}'),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
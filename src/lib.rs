@@ -2,90 +2,401 @@
 //! Checker for pytest setUp/tearDown method usage.
 //!
 //! This module provides validation that Python test classes correctly implement setUp/tearDown
-//! methods with proper naming (camelCase) and with super() calls as the last statement.
+//! (and the class-level setUpClass/tearDownClass/setUpTestData and async
+//! asyncSetUp/asyncTearDown) methods with proper naming (camelCase) and with super() calls as
+//! the last statement, and that unittest modules implement setUpModule/tearDownModule properly.
+//!
+//! pytest's xunit-style hooks (`setup_method`, `setup_module`, ...) are checked by the
+//! [`xunit`] module.
+
+use ruff_python_ast::visitor::{walk_expr, Visitor};
+use ruff_python_ast::{Expr, ExprCall, Stmt, StmtClassDef, StmtFunctionDef};
+use ruff_text_size::{Ranged, TextRange, TextSize};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    mem,
+    path::Path,
+    str::FromStr,
+};
 
-use ruff_python_ast::Stmt;
-use ruff_python_parser::parse_module;
-use ruff_text_size::Ranged;
-use std::{fs, path::Path};
+pub use classes::DEFAULT_TEST_CASE_ROOTS;
+pub use diagnostic::{Diagnostic, Edit, Fix, Label, Location};
+pub use hierarchy::CHAINED_METHODS;
+use index::{ClassRef, LoadError, Project, ProjectIndex};
+use line_index::LineIndex;
+pub use report::{CheckedMethod, Report};
+pub use rules::{select_rules, Rule, RuleInfo, RuleSelector, Severity, RULES};
 
+mod attributes;
+mod ci;
+mod classes;
+mod diagnostic;
 pub mod fix;
+mod flow;
+mod hierarchy;
+mod index;
+mod junit;
+mod line_index;
+mod mro;
+pub mod output;
+mod report;
+mod rules;
+mod sarif;
+mod teardown;
+mod xunit;
+
+/// The level a function is defined at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Scope {
+    /// A top-level function
+    Module,
+    /// A method of a class
+    Class,
+}
+
+/// Options for checking and fixing files.
+///
+/// The default enables every check that runs without opting in.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Flag `super(Cls, self).setUp()` and `Base.setUp(self)` in favour of `super().setUp()`;
+    /// with `--fix`, rewrite them when `Cls` / `Base` is the enclosing class / its first base
+    pub require_zero_arg_super: bool,
+    /// Where each lifecycle method has to call its parent, keyed by method name; methods not
+    /// listed default to [`SuperPosition::Last`]
+    pub super_positions: HashMap<String, SuperPosition>,
+    /// Flag tearDowns whose cleanup can raise before an unprotected super() call, so the
+    /// parent's cleanup would never run; with `--fix`, wrap the cleanup in `try:` / `finally:`
+    pub require_robust_teardown: bool,
+    /// Qualified names of the TestCase classes whose subclasses are test classes; only test
+    /// classes get the lifecycle checks. Defaults to [`DEFAULT_TEST_CASE_ROOTS`]
+    pub test_case_roots: Vec<String>,
+    /// The rules whose violations are reported and fixed; see [`select_rules`]. Defaults to
+    /// the rules that run without opting in
+    pub rules: HashSet<Rule>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            require_zero_arg_super: false,
+            super_positions: HashMap::new(),
+            require_robust_teardown: false,
+            test_case_roots: DEFAULT_TEST_CASE_ROOTS
+                .iter()
+                .map(|root| root.to_string())
+                .collect(),
+            rules: select_rules(None, &[], &[]),
+        }
+    }
+}
+
+impl Config {
+    /// Check if violations of `rule` are reported and fixed.
+    ///
+    /// The opt-in flags [`Config::require_zero_arg_super`] and
+    /// [`Config::require_robust_teardown`] enable their rules too.
+    pub fn is_enabled(&self, rule: Rule) -> bool {
+        match rule {
+            Rule::LegacySuper if self.require_zero_arg_super => true,
+            Rule::FragileTeardown if self.require_robust_teardown => true,
+            _ => self.rules.contains(&rule),
+        }
+    }
+
+    /// Return where `method` has to call its parent.
+    pub fn super_position(&self, method: &str) -> SuperPosition {
+        self.super_positions
+            .get(method)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// Where a lifecycle method has to call its parent's method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SuperPosition {
+    /// Before anything else in the method (after its docstring), e.g. for Django's
+    /// `setUpClass`, which has to set up the database before the subclass uses it
+    First,
+    /// After everything else in the method
+    #[default]
+    Last,
+    /// Anywhere, as long as it is a top-level statement of the method and so always runs
+    Anywhere,
+}
+
+impl SuperPosition {
+    /// Describe the requirement, completing "super().setUp() must be ...".
+    fn requirement(self) -> &'static str {
+        match self {
+            SuperPosition::First => "the first line",
+            SuperPosition::Last => "the last line",
+            SuperPosition::Anywhere => "called unconditionally",
+        }
+    }
+}
+
+impl FromStr for SuperPosition {
+    type Err = String;
+
+    /// Parse `first`, `last` or `anywhere-unconditional`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "first" => Ok(SuperPosition::First),
+            "last" => Ok(SuperPosition::Last),
+            "anywhere-unconditional" => Ok(SuperPosition::Anywhere),
+            _ => Err(format!(
+                "invalid super position '{}': expected first, last or anywhere-unconditional",
+                s
+            )),
+        }
+    }
+}
+
+/// State shared by the checks while walking one file.
+pub(crate) struct Checker<'a> {
+    path: &'a Path,
+    src: &'a str,
+    /// Where the lines of `src` start
+    line_index: &'a LineIndex,
+    config: &'a Config,
+    /// Whether the file is written against unittest rather than plain pytest
+    unittest_module: bool,
+    /// The project's files, for resolving class bases
+    index: &'a ProjectIndex<'a>,
+    /// The index of the file being checked in the project
+    file: usize,
+    /// The class whose body is being checked, if any
+    class: Option<&'a StmtClassDef>,
+    /// Whether that class is a unittest-style test class
+    test_case: bool,
+    /// The method or function being checked, if any
+    method: Option<&'a str>,
+    diagnostics: Vec<Diagnostic>,
+    /// The lifecycle methods and hooks checked so far
+    methods: Vec<CheckedMethod>,
+}
+
+impl Checker<'_> {
+    /// Record a violation of `rule` spanning `node`, in the current class and method.
+    ///
+    /// Returns the diagnostic, so the caller can attach a fix.
+    pub(crate) fn report(
+        &mut self,
+        rule: Rule,
+        node: impl Ranged,
+        message: impl Display,
+    ) -> &mut Diagnostic {
+        self.diagnostics.push(Diagnostic {
+            rule,
+            severity: rule.severity(),
+            file: self.path.to_path_buf(),
+            start: self.line_index.location(self.src, node.start().to_usize()),
+            end: self.line_index.location(self.src, node.end().to_usize()),
+            message: message.to_string(),
+            labels: Vec::new(),
+            fix: None,
+            class: self.class.map(|class_def| class_def.name.to_string()),
+            method: self.method.map(str::to_string),
+        });
+        self.diagnostics.last_mut().unwrap()
+    }
+
+    /// Record that the lifecycle method or hook `func_def`, in the current class, was checked.
+    pub(crate) fn checked(&mut self, func_def: &StmtFunctionDef) {
+        self.methods.push(CheckedMethod {
+            file: self.path.to_path_buf(),
+            class: self.class.map(|class_def| class_def.name.to_string()),
+            method: func_def.name.to_string(),
+            start: self
+                .line_index
+                .location(self.src, func_def.name.start().to_usize()),
+        });
+    }
+
+    /// Return a label for `node`, to attach to a diagnostic.
+    pub(crate) fn label(&self, node: impl Ranged, message: impl Display) -> Label {
+        Label {
+            start: self.line_index.location(self.src, node.start().to_usize()),
+            end: self.line_index.location(self.src, node.end().to_usize()),
+            message: message.to_string(),
+        }
+    }
+}
 
 /// Check a Python file for setUp/tearDown method violations.
 ///
-/// Returns a list of error messages, one per violation found.
+/// Returns a list of diagnostics, one per violation found.
 /// Returns empty vector if no violations are found.
-pub fn check_file(path: &Path) -> Vec<String> {
-    let Ok(src) = fs::read_to_string(path) else {
-        return vec![];
-    };
-    let Ok(parsed) = parse_module(&src) else {
-        return vec![];
+pub fn check_file(path: &Path) -> Vec<Diagnostic> {
+    check_file_with_config(path, &Config::default())
+}
+
+/// Check a Python file for setUp/tearDown method violations using the given options.
+pub fn check_file_with_config(path: &Path, config: &Config) -> Vec<Diagnostic> {
+    check_files_with_config(&[path], config)
+}
+
+/// Check several Python files for setUp/tearDown method violations using the given options.
+///
+/// The files, and the project files they import, are parsed once up front, so that test
+/// classes deriving from a base class in another file (`tests/base.py`) are recognized.
+/// Returns the violations of all given files, in order.
+pub fn check_files_with_config(paths: &[&Path], config: &Config) -> Vec<Diagnostic> {
+    report_files_with_config(paths, config).diagnostics
+}
+
+/// Check several Python files like [`check_files_with_config`], also listing the lifecycle
+/// methods and hooks that were checked, including those without violations.
+pub fn report_files_with_config(paths: &[&Path], config: &Config) -> Report {
+    let project = Project::load(paths);
+    let index = ProjectIndex::new(&project);
+
+    let mut report = Report::default();
+    for file in 0..project.given {
+        check_source(&index, file, config, &mut report);
+    }
+    report
+}
+
+/// Check one file of the project index, adding what it finds to `report`.
+fn check_source(index: &ProjectIndex, file: usize, config: &Config, report: &mut Report) {
+    let source = &index.files[file];
+    let body = &source.module.body;
+
+    let mut checker = Checker {
+        path: &source.path,
+        src: &source.src,
+        line_index: &source.line_index,
+        config,
+        unittest_module: is_unittest_module(index, file, &config.test_case_roots),
+        index,
+        file,
+        class: None,
+        test_case: false,
+        method: None,
+        diagnostics: Vec::new(),
+        methods: Vec::new(),
     };
 
-    let mut errors = Vec::new();
+    check_load_errors(&mut checker, &source.errors);
 
     // Recursively check all top-level statements (including classes)
-    for stmt in parsed.syntax().body.iter() {
-        check_stmt(stmt, path, &mut errors, &src);
+    for stmt in body.iter() {
+        check_stmt(&mut checker, stmt, Scope::Module);
     }
+    check_module_teardown(&mut checker, body);
 
-    errors
+    checker
+        .diagnostics
+        .retain(|diagnostic| config.is_enabled(diagnostic.rule));
+
+    // `--fix` leaves files it couldn't read or parse cleanly alone. Otherwise, attach the
+    // rewrite of the enclosing function to each fix
+    if !source.errors.is_empty() {
+        for diagnostic in &mut checker.diagnostics {
+            diagnostic.fix = None;
+        }
+    } else if checker.diagnostics.iter().any(|d| d.fix.is_some()) {
+        let edits = fix::source_edits(index, file, config);
+        for diagnostic in &mut checker.diagnostics {
+            if let Some(fix) = &mut diagnostic.fix {
+                fix.edits.extend(
+                    edits
+                        .iter()
+                        .rev()
+                        .filter(|edit| {
+                            edit.start <= diagnostic.start && diagnostic.start < edit.end
+                        })
+                        .cloned(),
+                );
+            }
+        }
+    }
+    report.diagnostics.append(&mut checker.diagnostics);
+    report.methods.append(&mut checker.methods);
+}
+
+/// Report why a file couldn't be read or parsed cleanly.
+fn check_load_errors(checker: &mut Checker, errors: &[LoadError]) {
+    for error in errors {
+        match error {
+            LoadError::Io(err) => {
+                checker.report(
+                    Rule::IoError,
+                    TextRange::default(),
+                    format_args!("can't read file: {}", err),
+                );
+            }
+            LoadError::Decode(offset) => {
+                // The undecodable bytes were replaced with U+FFFD
+                let start = TextSize::try_from(*offset).unwrap_or_default();
+                checker.report(
+                    Rule::DecodeError,
+                    TextRange::at(start, TextSize::of("\u{FFFD}")),
+                    "file is not valid UTF-8",
+                );
+            }
+            LoadError::Syntax(err) => {
+                checker.report(
+                    Rule::SyntaxError,
+                    err.location,
+                    format_args!("syntax error: {}", err.error),
+                );
+            }
+        }
+    }
 }
 
 /// Recursively check a statement for setUp/tearDown violations.
 ///
-/// - For function definitions: checks method name and super() call placement
-/// - For class definitions: recursively checks all methods in the class
-fn check_stmt(stmt: &Stmt, path: &Path, errors: &mut Vec<String>, src: &str) {
+/// - For methods of test classes: checks method name and super() call placement
+/// - For other functions: checks module fixtures and pytest xunit hooks
+/// - For class definitions: recursively checks the methods of test classes
+fn check_stmt<'a>(checker: &mut Checker<'a>, stmt: &'a Stmt, scope: Scope) {
     match stmt {
         Stmt::FunctionDef(func_def) => {
-            match func_def.name.as_str() {
-                "setUp" | "tearDown" => {
-                    // Check properly-cased setUp/tearDown methods
-                    if func_def.body.is_empty() {
-                        return;
-                    }
-
-                    // Skip validation for methods that only contain 'pass'
-                    if func_def.body.len() == 1 {
-                        if let Stmt::Pass(_) = &func_def.body[0] {
-                            return;
-                        }
-                    }
-
-                    let last = func_def.body.last().unwrap();
-                    let expected = func_def.name.as_str();
-
-                    if !is_super_call_last(last, expected) {
-                        let line = src[..stmt.start().to_usize()].lines().count();
-                        errors.push(format!(
-                            "{}:{} super().{}() must be the last line",
-                            path.display(),
-                            line,
-                            expected
-                        ));
-                    }
-                }
-                "setup" | "teardown" | "Setup" | "Teardown" => {
-                    // Flag incorrectly-cased setUp/tearDown methods
-                    let line = src[..stmt.start().to_usize()].lines().count();
-                    errors.push(format!(
-                        "{}:{} use correct casing: setUp / tearDown",
-                        path.display(),
-                        line
-                    ));
-                }
-                _ => {
-                    // Other methods are not checked
-                }
+            let outer_method = checker.method.replace(func_def.name.as_str());
+            if scope == Scope::Class && checker.test_case {
+                check_method(checker, stmt, func_def, scope);
+            } else {
+                check_function(checker, stmt, func_def, scope);
             }
+            checker.method = outer_method;
         }
         Stmt::ClassDef(class_def) => {
-            // Recursively check all methods in the class
+            let class = ClassRef {
+                file: checker.file,
+                class_def,
+            };
+            let test_case = checker
+                .index
+                .is_test_class(class, &checker.config.test_case_roots);
+            // pytest collects plain `Test*` classes, which can only use its xunit hooks
+            let pytest_class = !test_case && class_def.name.as_str().starts_with("Test");
+
+            let outer_class = checker.class.replace(class_def);
+            let outer_method = checker.method.take();
+
+            // Test classes are also checked against their ancestors: for broken super() chains
+            // and for attributes read or assigned before the ancestors' setUp assigns them
+            if test_case {
+                hierarchy::check_inherited_chains(checker, stmt, class);
+                attributes::check_setup_attributes(checker, class);
+            }
+
+            // Recursively check the methods of test classes, and any nested classes
+            let outer_test_case = mem::replace(&mut checker.test_case, test_case);
             for nested_stmt in &class_def.body {
-                check_stmt(nested_stmt, path, errors, src);
+                if test_case || pytest_class || matches!(nested_stmt, Stmt::ClassDef(_)) {
+                    check_stmt(checker, nested_stmt, Scope::Class);
+                }
             }
+            checker.class = outer_class;
+            checker.method = outer_method;
+            checker.test_case = outer_test_case;
         }
         _ => {
             // Other statement types are ignored
@@ -93,41 +404,658 @@ fn check_stmt(stmt: &Stmt, path: &Path, errors: &mut Vec<String>, src: &str) {
     }
 }
 
-/// Check if the last statement in a method is a super() call to the expected method.
+/// Check a method of a test class: lifecycle methods for their name, declaration and super()
+/// call, anything else as a possible module fixture or pytest hook.
+fn check_method(checker: &mut Checker, stmt: &Stmt, func_def: &StmtFunctionDef, scope: Scope) {
+    let fixable = is_fixable(checker.line_index, func_def);
+    match func_def.name.as_str() {
+        "setUp" | "tearDown" => {
+            // Check properly-cased setUp/tearDown methods
+            checker.checked(func_def);
+            check_not_async(checker, stmt, func_def);
+            check_super_call(checker, func_def);
+        }
+        "setUpClass" | "tearDownClass" | "setUpTestData" => {
+            // Class-level fixtures (including Django's setUpTestData) only chain correctly as
+            // classmethods
+            checker.checked(func_def);
+            if !is_classmethod(func_def) {
+                let diagnostic = checker.report(
+                    Rule::MissingClassmethod,
+                    stmt,
+                    format_args!("{} must be decorated with @classmethod", func_def.name),
+                );
+                if fixable {
+                    diagnostic.fix = Some(Fix::new("Add @classmethod"));
+                }
+            }
+            check_not_async(checker, stmt, func_def);
+            check_super_call(checker, func_def);
+        }
+        "asyncSetUp" | "asyncTearDown" => {
+            // IsolatedAsyncioTestCase awaits these hooks, so they must be coroutines
+            checker.checked(func_def);
+            if !func_def.is_async {
+                let diagnostic = checker.report(
+                    Rule::AsyncHookNotCoroutine,
+                    stmt,
+                    format_args!("{} must be declared with async def", func_def.name),
+                );
+                if fixable {
+                    diagnostic.fix = Some(Fix::new("Declare with async def"));
+                }
+            }
+            check_super_call(checker, func_def);
+        }
+        "setUpModule" | "tearDownModule" => {
+            check_module_fixture(checker, stmt, func_def, scope);
+        }
+        name => {
+            let miscased = match name {
+                "setup" | "Setup" => Some(("setUp", "setUp / tearDown")),
+                "teardown" | "Teardown" => Some(("tearDown", "setUp / tearDown")),
+                _ => [
+                    ("setUpClass", "setUpClass / tearDownClass"),
+                    ("tearDownClass", "setUpClass / tearDownClass"),
+                    ("setUpTestData", "setUpTestData"),
+                    ("asyncSetUp", "asyncSetUp / asyncTearDown"),
+                    ("asyncTearDown", "asyncSetUp / asyncTearDown"),
+                ]
+                .into_iter()
+                .find(|(canonical, _)| is_miscased(name, canonical)),
+            };
+            if let Some((canonical, names)) = miscased {
+                // Flag incorrectly-cased lifecycle methods
+                checker.checked(func_def);
+                let diagnostic = checker.report(
+                    Rule::MethodCasing,
+                    func_def.name.range(),
+                    format_args!("use correct casing: {}", names),
+                );
+                if fixable {
+                    diagnostic.fix = Some(Fix::new(format_args!("Rename to {}", canonical)));
+                }
+            } else {
+                // Other methods only matter if they are module fixtures or pytest xunit-style
+                // hooks
+                check_function(checker, stmt, func_def, scope);
+            }
+        }
+    }
+}
+
+/// Check a function outside a test class, or a method that isn't a lifecycle method: outside
+/// test classes only module fixtures and pytest's hooks have a meaning.
+fn check_function(checker: &mut Checker, stmt: &Stmt, func_def: &StmtFunctionDef, scope: Scope) {
+    match func_def.name.as_str() {
+        "setUpModule" | "tearDownModule" => {
+            check_module_fixture(checker, stmt, func_def, scope);
+        }
+        name if checker.unittest_module => match module_fixture_misspelling(name) {
+            // In unittest modules, setupModule and pytest's setup_module are typos for
+            // setUpModule
+            Some(canonical) => {
+                checker.checked(func_def);
+                checker
+                    .report(
+                        Rule::MethodCasing,
+                        func_def.name.range(),
+                        "use correct casing: setUpModule / tearDownModule",
+                    )
+                    .fix = Some(Fix::new(format_args!("Rename to {}", canonical)));
+            }
+            None => xunit::check_xunit_hook(checker, stmt, func_def, scope),
+        },
+        _ => {
+            xunit::check_xunit_hook(checker, stmt, func_def, scope);
+        }
+    }
+}
+
+/// Check if `--fix` rewrites a lifecycle method: it leaves empty and pass-only methods, and
+/// methods defined on one line (`def setUp(self): ...`), alone.
+fn is_fixable(line_index: &LineIndex, func_def: &StmtFunctionDef) -> bool {
+    match &*func_def.body {
+        [] | [Stmt::Pass(_)] => false,
+        [first, ..] => {
+            line_index.line_number(first) > line_index.line_number(func_def.name.range())
+        }
+    }
+}
+
+/// Check that a lifecycle method calls super() to itself where its [`SuperPosition`] requires.
+///
+/// For `last` and `anywhere-unconditional` every normal exit path has to make the call, so
+/// the report names the branches of a path that skips it. Empty and pass-only methods are
+/// skipped: they do nothing that needs chaining.
+fn check_super_call(checker: &mut Checker, func_def: &StmtFunctionDef) {
+    if func_def.body.is_empty() {
+        return;
+    }
+
+    // Skip validation for methods that only contain 'pass'
+    if func_def.body.len() == 1 {
+        if let Stmt::Pass(_) = &func_def.body[0] {
+            return;
+        }
+    }
+
+    let expected = func_def.name.as_str();
+    let position = checker.config.super_position(expected);
+    let call_text = format!(
+        "{}super().{}()",
+        if is_async_hook(expected) {
+            "await "
+        } else {
+            ""
+        },
+        expected
+    );
+    let requirement = format!("{} must be {}", call_text, position.requirement());
+
+    // Follow every path, so that calls inside branches count and early returns don't slip
+    // through. `--fix` only moves top-level calls, as moving the others line by line could
+    // empty a branch or call the parent twice
+    let analysis = flow::analyze(&func_def.body, expected, checker.src, checker.line_index);
+    let top_level_calls = func_def
+        .body
+        .iter()
+        .filter(|stmt| super_call(stmt, expected).is_some())
+        .count();
+    let fixable =
+        is_fixable(checker.line_index, func_def) && analysis.calls.len() == top_level_calls;
+    // `--fix` puts the call at the end, which paths returning before it never reach
+    let movable = fixable && (position == SuperPosition::First || !analysis.returns);
+    let fix = Fix::new(format_args!(
+        "{} {} {}",
+        if analysis.calls.is_empty() {
+            "Add"
+        } else {
+            "Move"
+        },
+        call_text,
+        match position {
+            SuperPosition::First => "to the start",
+            SuperPosition::Last | SuperPosition::Anywhere => "to the end",
+        }
+    ));
+
+    // Position errors point at the statement in the way of the call, labelling the call
+    // closest before it, or at the method's name when there is no call to move
+    let name = func_def.name.range();
+    let found = |checker: &Checker, at: TextRange| {
+        let call = analysis
+            .calls
+            .iter()
+            .rev()
+            .find(|call| call.call.start() < at.start())
+            .or(analysis.calls.first())?;
+        Some(checker.label(
+            call.call,
+            format_args!("{} found here", snippet(checker.src, call.call)),
+        ))
+    };
+    let calls = match position {
+        SuperPosition::First => {
+            let Some(call) = positioned_super_call(&func_def.body, expected, position) else {
+                let first_stmt = func_def.body.iter().find(|stmt| !is_docstring(stmt));
+                let at = match first_stmt {
+                    Some(first_stmt) if !analysis.calls.is_empty() => first_stmt.range(),
+                    _ => name,
+                };
+                let label = found(checker, at);
+                let diagnostic = checker.report(Rule::SuperPosition, at, requirement);
+                diagnostic.labels.extend(label);
+                if movable {
+                    diagnostic.fix = Some(fix);
+                }
+                return;
+            };
+            vec![call]
+        }
+        SuperPosition::Last | SuperPosition::Anywhere => {
+            if let Some((path, problem)) = analysis.violation(position) {
+                let at = match path.culprit {
+                    Some(culprit) if !analysis.calls.is_empty() => culprit,
+                    _ => name,
+                };
+                let label = found(checker, at);
+                let diagnostic = if path.steps.is_empty() {
+                    checker.report(Rule::SuperPosition, at, requirement)
+                } else {
+                    checker.report(
+                        Rule::SuperPosition,
+                        at,
+                        format_args!(
+                            "{}; {} when {}",
+                            requirement,
+                            problem,
+                            path.steps.join(" and ")
+                        ),
+                    )
+                };
+                diagnostic.labels.extend(label);
+                if movable {
+                    diagnostic.fix = Some(fix);
+                }
+                return;
+            }
+            analysis.calls
+        }
+    };
+
+    if let Some(call) = calls
+        .iter()
+        .find(|call| is_async_hook(expected) && !call.awaited)
+    {
+        // A bare call only creates the parent's coroutine; it never runs
+        let diagnostic = checker.report(
+            Rule::UnawaitedSuper,
+            call.call,
+            format_args!("super().{}() must be awaited", expected),
+        );
+        if movable {
+            diagnostic.fix = Some(Fix::new(format_args!("Await super().{}()", expected)));
+        }
+    } else if let Some(call) = calls
+        .iter()
+        .find(|call| !matches!(call.form, SuperForm::ZeroArg))
+    {
+        // Legacy forms chain correctly, so they are only flagged on request
+        if checker.config.is_enabled(Rule::LegacySuper) {
+            check_legacy_super(checker, call, expected);
+        }
+    }
+    if checker.config.is_enabled(Rule::FragileTeardown) && teardown::is_teardown(expected) {
+        teardown::check_robust_teardown(
+            checker,
+            func_def,
+            fixable && position != SuperPosition::First,
+        );
+    }
+}
+
+/// Flag a `super(Cls, self).setUp()` or `Base.setUp(self)` call.
+///
+/// A `super()` naming some class other than the enclosing one skips part of the MRO, so it is
+/// reported as such rather than as a plain modernization.
+fn check_legacy_super(checker: &mut Checker, call: &SuperCall, expected: &str) {
+    let src = checker.src;
+    let text = &src[call.call.range()];
+    let class = checker.class;
+    if let (SuperForm::Legacy(Expr::Name(name)), Some(class_def)) = (call.form, class) {
+        if name.id.as_str() != class_def.name.as_str() {
+            checker.report(
+                Rule::LegacySuper,
+                call.call,
+                format_args!(
+                    "{} does not name the enclosing class {}",
+                    text, class_def.name
+                ),
+            );
+            return;
+        }
+    }
+    let diagnostic = checker.report(
+        Rule::LegacySuper,
+        call.call,
+        format_args!("use super().{}() instead of {}", expected, text),
+    );
+    // Only calls on one line are rewritten
+    if class.is_some_and(|class_def| call.form.is_modernizable(class_def, src))
+        && !text.contains('\n')
+    {
+        diagnostic.fix = Some(Fix::new(format_args!(
+            "Replace with super().{}()",
+            expected
+        )));
+    }
+}
+
+/// Check that a synchronous lifecycle method isn't declared with `async def`.
+///
+/// unittest calls these hooks without awaiting them, so an `async def setUp` silently
+/// never runs its body.
+fn check_not_async(checker: &mut Checker, stmt: &Stmt, func_def: &StmtFunctionDef) {
+    if !func_def.is_async {
+        return;
+    }
+
+    let hint = match func_def.name.as_str() {
+        "setUp" => "; use asyncSetUp",
+        "tearDown" => "; use asyncTearDown",
+        _ => "",
+    };
+    checker.report(
+        Rule::SyncHookCoroutine,
+        stmt,
+        format_args!(
+            "{} must not be declared with async def{}",
+            func_def.name, hint
+        ),
+    );
+}
+
+/// Check a unittest module fixture (setUpModule/tearDownModule).
+///
+/// unittest only looks these up at module level and calls them without arguments.
+fn check_module_fixture(
+    checker: &mut Checker,
+    stmt: &Stmt,
+    func_def: &StmtFunctionDef,
+    scope: Scope,
+) {
+    checker.checked(func_def);
+    if scope != Scope::Module {
+        checker.report(
+            Rule::ModuleFixturePlacement,
+            stmt,
+            format_args!("{} must be defined at module level", func_def.name),
+        );
+    }
+
+    let parameters = &func_def.parameters;
+    let mut positional = parameters.posonlyargs.iter().chain(&parameters.args);
+    if positional
+        .clone()
+        .next()
+        .is_some_and(|param| param.parameter.name.as_str() == "self")
+    {
+        checker.report(
+            Rule::ModuleFixtureSignature,
+            stmt,
+            format_args!("{} must not take a self parameter", func_def.name),
+        );
+    } else if positional.any(|param| param.default.is_none())
+        || parameters
+            .kwonlyargs
+            .iter()
+            .any(|param| param.default.is_none())
+    {
+        checker.report(
+            Rule::ModuleFixtureSignature,
+            stmt,
+            format_args!("{} must not take arguments", func_def.name),
+        );
+    }
+}
+
+/// Warn when a module sets up module-level state it never tears down.
+///
+/// `setUpModule` needs either a `tearDownModule` or an `addModuleCleanup` registration,
+/// otherwise whatever it creates outlives the module's tests.
+fn check_module_teardown(checker: &mut Checker, body: &[Stmt]) {
+    let mut setup = None;
+    let mut has_teardown = false;
+    for stmt in body {
+        if let Stmt::FunctionDef(func_def) = stmt {
+            match func_def.name.as_str() {
+                "setUpModule" => setup = Some(stmt),
+                "tearDownModule" => has_teardown = true,
+                _ => {}
+            }
+        }
+    }
+
+    let Some(setup) = setup else {
+        return;
+    };
+    if has_teardown {
+        return;
+    }
+
+    let mut finder = CallFinder {
+        name: "addModuleCleanup",
+        found: false,
+    };
+    finder.visit_body(body);
+    if !finder.found {
+        let outer_method = checker.method.replace("setUpModule");
+        checker.report(
+            Rule::MissingModuleTeardown,
+            setup,
+            "setUpModule has no matching tearDownModule or addModuleCleanup",
+        );
+        checker.method = outer_method;
+    }
+}
+
+/// Finds calls to a function or method by name, e.g. `addModuleCleanup(...)` or
+/// `unittest.addModuleCleanup(...)`.
+struct CallFinder<'n> {
+    name: &'n str,
+    found: bool,
+}
+
+impl<'a> Visitor<'a> for CallFinder<'_> {
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        if let Expr::Call(call) = expr {
+            let callee = match &*call.func {
+                Expr::Name(name) => Some(name.id.as_str()),
+                Expr::Attribute(attr) => Some(attr.attr.as_str()),
+                _ => None,
+            };
+            if callee == Some(self.name) {
+                self.found = true;
+            }
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Check if a module is written against unittest rather than plain pytest.
+///
+/// A module counts as unittest-based if it imports `unittest` (other than `unittest.mock`) or
+/// `django.test`, or defines a test class, i.e. one deriving from one of the `roots` (see
+/// [`ProjectIndex::is_test_class`]).
+pub(crate) fn is_unittest_module(index: &ProjectIndex, file: usize, roots: &[String]) -> bool {
+    fn is_unittest_import(module: &str) -> bool {
+        let is_within = |root: &str| {
+            module == root
+                || module
+                    .strip_prefix(root)
+                    .is_some_and(|rest| rest.starts_with('.'))
+        };
+        (is_within("unittest") && !is_within("unittest.mock")) || is_within("django.test")
+    }
+
+    index.files[file].module.body.iter().any(|stmt| match stmt {
+        Stmt::Import(import) => import
+            .names
+            .iter()
+            .any(|alias| is_unittest_import(alias.name.as_str())),
+        Stmt::ImportFrom(import) => import
+            .module
+            .as_ref()
+            .is_some_and(|module| is_unittest_import(module.as_str())),
+        Stmt::ClassDef(class_def) => index.is_test_class(ClassRef { file, class_def }, roots),
+        _ => false,
+    })
+}
+
+/// Check if `name` is one of the IsolatedAsyncioTestCase coroutine hooks.
+pub(crate) fn is_async_hook(name: &str) -> bool {
+    matches!(name, "asyncSetUp" | "asyncTearDown")
+}
+
+/// Check if `name` is a class-level fixture that must be a `@classmethod`.
+pub(crate) fn is_class_fixture(name: &str) -> bool {
+    matches!(name, "setUpClass" | "tearDownClass" | "setUpTestData")
+}
+
+/// Return the unittest module fixture that `name` misspells, if any.
 ///
-/// Verifies the pattern: `super().setUp()` or `super().tearDown()`
+/// Case and underscores are ignored, so `setupModule`, `setup_module` and `teardown_module`
+/// all count.
+pub(crate) fn module_fixture_misspelling(name: &str) -> Option<&'static str> {
+    ["setUpModule", "tearDownModule"]
+        .into_iter()
+        .find(|canonical| name != *canonical && normalize_name(name) == normalize_name(canonical))
+}
+
+/// Lowercase `name` and drop its underscores, so `setup_Method` and `setupMethod` compare
+/// equal.
+pub(crate) fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '_')
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Check if `name` is a miscased spelling of `canonical`.
+///
+/// For example `setupClass` and `teardownclass` are miscased spellings of
+/// `setUpClass` and `tearDownClass`; the canonical name itself is not.
+pub(crate) fn is_miscased(name: &str, canonical: &str) -> bool {
+    name != canonical && name.eq_ignore_ascii_case(canonical)
+}
+
+/// Return the source text of `node`, shortened to its first line.
+pub(crate) fn snippet(src: &str, node: impl Ranged) -> String {
+    let text = &src[node.range()];
+    match text.split_once('\n') {
+        Some((first, _)) => format!("{} ...", first.trim_end()),
+        None => text.to_string(),
+    }
+}
+
+/// Check if a function is decorated with `@classmethod`.
+pub(crate) fn is_classmethod(func_def: &StmtFunctionDef) -> bool {
+    func_def
+        .decorator_list
+        .iter()
+        .any(|decorator| {
+            matches!(&decorator.expression, Expr::Name(name) if name.id.as_str() == "classmethod")
+        })
+}
+
+/// How a statement invokes the parent's lifecycle method.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SuperCall<'a> {
+    /// Whether the call is awaited, as in `await super().asyncSetUp()`
+    pub(crate) awaited: bool,
+    pub(crate) form: SuperForm<'a>,
+    /// The call itself, without any `await`
+    pub(crate) call: &'a ExprCall,
+}
+
+/// The spelling of a call to the parent's lifecycle method.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum SuperForm<'a> {
+    /// `super().setUp()`
+    ZeroArg,
+    /// `super(Cls, self).setUp()`, holding `Cls`
+    Legacy(&'a Expr),
+    /// `Base.setUp(self)`, holding `Base`
+    Explicit(&'a Expr),
+}
+
+impl SuperForm<'_> {
+    /// Check if the call can be rewritten to `super().{expected}()` without changing which
+    /// method it reaches.
+    ///
+    /// That holds for `super(Cls, self)` naming the enclosing class, and for `Base.setUp(self)`
+    /// where `Base` is the class's first base.
+    pub(crate) fn is_modernizable(&self, class_def: &StmtClassDef, src: &str) -> bool {
+        match self {
+            SuperForm::ZeroArg => false,
+            SuperForm::Legacy(cls) => {
+                matches!(cls, Expr::Name(name) if name.id.as_str() == class_def.name.as_str())
+            }
+            SuperForm::Explicit(base) => class_def
+                .bases()
+                .first()
+                .is_some_and(|first| src[first.range()] == src[base.range()]),
+        }
+    }
+}
+
+/// Find the call to the parent's `expected` method at the place `position` requires.
 ///
 /// # Arguments
-/// * `stmt` - The statement to check (should be the last in a method body)
-/// * `expected` - The expected method name ("setUp" or "tearDown")
+/// * `body` - The lifecycle method's body
+/// * `expected` - The expected method name (e.g. "setUp" or "tearDownClass")
+/// * `position` - Where the call has to be
+pub(crate) fn positioned_super_call<'a>(
+    body: &'a [Stmt],
+    expected: &str,
+    position: SuperPosition,
+) -> Option<SuperCall<'a>> {
+    match position {
+        SuperPosition::First => body
+            .iter()
+            .find(|stmt| !is_docstring(stmt))
+            .and_then(|stmt| super_call(stmt, expected)),
+        SuperPosition::Last => body.last().and_then(|stmt| super_call(stmt, expected)),
+        SuperPosition::Anywhere => body.iter().find_map(|stmt| super_call(stmt, expected)),
+    }
+}
+
+/// Check if a statement is a docstring, i.e. a bare string literal.
+pub(crate) fn is_docstring(stmt: &Stmt) -> bool {
+    matches!(stmt, Stmt::Expr(expr_stmt) if matches!(&*expr_stmt.value, Expr::StringLiteral(_)))
+}
+
+/// Check if a statement in a method is a call to the parent's method of the same name.
+///
+/// Verifies the pattern: `super().setUp()` or `await super().asyncSetUp()`, and recognizes the
+/// legacy spellings `super(Cls, self).setUp()` and `Base.setUp(self)`
+///
+/// # Arguments
+/// * `stmt` - The statement to check
+/// * `expected` - The expected method name (e.g. "setUp" or "tearDownClass")
 ///
 /// # Returns
-/// true if the statement matches `super().{expected}()`
-fn is_super_call_last(stmt: &Stmt, expected: &str) -> bool {
-    use ruff_python_ast::Expr;
-
+/// How the statement calls the parent's `expected` method, or `None` if it doesn't
+pub(crate) fn super_call<'a>(stmt: &'a Stmt, expected: &str) -> Option<SuperCall<'a>> {
     let Stmt::Expr(expr_stmt) = stmt else {
-        return false;
+        return None;
+    };
+
+    // Unwrap `await super().asyncSetUp()`
+    let (value, awaited) = match &*expr_stmt.value {
+        Expr::Await(await_expr) => (&*await_expr.value, true),
+        value => (value, false),
     };
-    let Expr::Call(call_expr) = &*expr_stmt.value else {
-        return false;
+
+    let Expr::Call(call_expr) = value else {
+        return None;
     };
     let Expr::Attribute(attr_expr) = &*call_expr.func else {
-        return false;
+        return None;
     };
 
     // Check attribute name matches what we expect (setUp or tearDown)
     if attr_expr.attr.as_str() != expected {
-        return false;
+        return None;
     }
 
-    // Check we're calling super().setUp() or super().tearDown()
-    let Expr::Call(super_call) = &*attr_expr.value else {
-        return false;
-    };
-    let Expr::Name(name_expr) = &*super_call.func else {
-        return false;
+    let form = match &*attr_expr.value {
+        // super().setUp() or super(Cls, self).setUp()
+        Expr::Call(super_call) => {
+            let Expr::Name(name_expr) = &*super_call.func else {
+                return None;
+            };
+            if name_expr.id.as_str() != "super" {
+                return None;
+            }
+            match &*super_call.arguments.args {
+                [] => SuperForm::ZeroArg,
+                [cls, _] => SuperForm::Legacy(cls),
+                _ => return None,
+            }
+        }
+        // Base.setUp(self)
+        base @ (Expr::Name(_) | Expr::Attribute(_)) => match &*call_expr.arguments.args {
+            [Expr::Name(arg)] if arg.id.as_str() == "self" => SuperForm::Explicit(base),
+            _ => return None,
+        },
+        _ => return None,
     };
 
-    name_expr.id.as_str() == "super"
+    Some(SuperCall {
+        awaited,
+        form,
+        call: call_expr,
+    })
 }
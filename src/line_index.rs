@@ -0,0 +1,53 @@
+// src/line_index.rs
+//! Mapping byte offsets to lines and columns.
+//!
+//! The parser reports byte offsets. Counting the newlines before an offset rescans the file
+//! for every violation, so each file's line starts are collected once, when it is loaded, and
+//! offsets are looked up with a binary search.
+
+use crate::Location;
+use ruff_text_size::Ranged;
+
+/// The start offsets of a file's lines.
+#[derive(Debug)]
+pub(crate) struct LineIndex {
+    /// The byte offset each line starts at; the first is always 0
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub(crate) fn new(src: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(src.match_indices('\n').map(|(newline, _)| newline + 1))
+            .collect();
+        LineIndex { line_starts }
+    }
+
+    /// Return the zero-based index of the line containing the byte `offset`.
+    pub(crate) fn line(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|&start| start <= offset) - 1
+    }
+
+    /// Return the one-based line `node` starts on.
+    pub(crate) fn line_number(&self, node: impl Ranged) -> usize {
+        self.line(node.start().to_usize()) + 1
+    }
+
+    /// Return the byte offset the zero-based `line` of `src` ends at, before its line break.
+    pub(crate) fn line_end(&self, src: &str, line: usize) -> usize {
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .map_or(src.len(), |&next| next - 1);
+        src[..end].strip_suffix('\r').map_or(end, str::len)
+    }
+
+    /// Return the location of the byte `offset` in `src`, the file the index was built from.
+    pub(crate) fn location(&self, src: &str, offset: usize) -> Location {
+        let line = self.line(offset);
+        Location {
+            line: line + 1,
+            column: src[self.line_starts[line]..offset].chars().count() + 1,
+        }
+    }
+}
@@ -4,46 +4,277 @@
 //! Usage:
 //! - Check files: pytest-super-hooks file1.py file2.py
 //! - Fix files: pytest-super-hooks --fix file1.py file2.py
+//! - Require `super()` over `super(Cls, self)`: pytest-super-hooks --require-zero-arg-super ...
+//! - Require tearDown's super() in a `finally:`: pytest-super-hooks --require-robust-teardown ...
+//! - Require super() first in setUpClass: pytest-super-hooks --super-position setUpClass=first ...
+//! - Treat subclasses of another base as test classes:
+//!   pytest-super-hooks --test-case-root myproject.testing.BaseTest ...
+//! - Choose the rules by code prefix: pytest-super-hooks --select PSH001,PSH002 --ignore PSH011 ...
+//! - List the rules, or explain one: pytest-super-hooks --list-rules / --explain PSH002
+//! - Show the offending code under each violation: pytest-super-hooks --output-format full ...
+//!   (colored on a terminal, unless `NO_COLOR` is set)
+//! - Report to tools: pytest-super-hooks --output-format json|jsonl|sarif ... (on stdout)
+//! - Annotate CI runs: pytest-super-hooks --output-format github|gitlab ... (on stdout)
+//! - Report every lifecycle method as a test: pytest-super-hooks --output-format junit ...
+//! - Report without failing: pytest-super-hooks --exit-zero ...
+//! - Fail when files were fixed, even if they are clean now:
+//!   pytest-super-hooks --fix --exit-non-zero-on-fix ...
+//!
+//! Exit codes:
+//! - 0: no violations (or `--exit-zero`)
+//! - 1: violations found, or files fixed with `--exit-non-zero-on-fix`
+//! - 2: usage error, such as an unknown option or an invalid value
+//! - 3: a file couldn't be read, decoded or parsed
+
+use pytest_super_hooks::{
+    fix::{fix_files_with_config, FixOutcome},
+    output::{code_frame, github, gitlab, json, json_lines, junit, sarif, OutputFormat, Run},
+    report_files_with_config, select_rules, Config, Diagnostic, Rule, RuleSelector,
+    CHAINED_METHODS, RULES,
+};
+use std::io::{self, IsTerminal};
+use std::process;
+use std::{collections::HashMap, env, fs, path::Path};
 
-use pytest_super_hooks::{check_file, fix::fix_file};
-use std::{env, path::Path};
+/// Exit code for violations found, or files fixed with `--exit-non-zero-on-fix`
+const EXIT_VIOLATIONS: i32 = 1;
+/// Exit code for invalid command lines
+const EXIT_USAGE: i32 = 2;
+/// Exit code for files that couldn't be read, decoded or parsed, or written back by `--fix`
+const EXIT_ERROR: i32 = 3;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
     if args.len() < 2 {
-        eprintln!("Usage: {} [--fix] <file1.py> [file2.py] ...", args[0]);
-        std::process::exit(1);
+        usage(&args[0]);
     }
 
     let mut fix_mode = false;
-    let mut errors = Vec::new();
+    let mut exit_zero = false;
+    let mut exit_non_zero_on_fix = false;
+    let mut output_format = OutputFormat::default();
+    let mut config = Config::default();
+    let mut files = Vec::new();
+    let mut select: Option<Vec<RuleSelector>> = None;
+    let mut extend_select = Vec::new();
+    let mut ignore = Vec::new();
 
-    // Parse arguments and process each Python file
-    for arg in &args[1..] {
-        if arg == "--fix" {
-            fix_mode = true;
-            continue;
+    // Parse all flags first so they apply to every file, wherever they appear
+    let mut args_iter = args[1..].iter();
+    while let Some(arg) = args_iter.next() {
+        match arg.as_str() {
+            "--fix" => fix_mode = true,
+            "--exit-zero" => exit_zero = true,
+            "--exit-non-zero-on-fix" => exit_non_zero_on_fix = true,
+            "--require-zero-arg-super" => extend_select.push(Rule::LegacySuper.into()),
+            "--require-robust-teardown" => extend_select.push(Rule::FragileTeardown.into()),
+            "--super-position" => {
+                let Some(value) = args_iter.next() else {
+                    usage(&args[0]);
+                };
+                let Some((method, position)) = value.split_once('=') else {
+                    eprintln!("--super-position expects METHOD=POSITION, got '{}'", value);
+                    process::exit(EXIT_USAGE);
+                };
+                if !CHAINED_METHODS.contains(&method) {
+                    eprintln!(
+                        "unknown lifecycle method '{}' (expected one of: {})",
+                        method,
+                        CHAINED_METHODS.join(", ")
+                    );
+                    process::exit(EXIT_USAGE);
+                }
+                match position.parse() {
+                    Ok(position) => {
+                        config.super_positions.insert(method.to_string(), position);
+                    }
+                    Err(err) => {
+                        eprintln!("{}", err);
+                        process::exit(EXIT_USAGE);
+                    }
+                }
+            }
+            "--output-format" => {
+                let Some(value) = args_iter.next() else {
+                    usage(&args[0]);
+                };
+                match value.parse() {
+                    Ok(format) => output_format = format,
+                    Err(err) => {
+                        eprintln!("{}", err);
+                        process::exit(EXIT_USAGE);
+                    }
+                }
+            }
+            "--test-case-root" => {
+                let Some(root) = args_iter.next() else {
+                    usage(&args[0]);
+                };
+                config.test_case_roots.push(root.clone());
+            }
+            "--select" | "--extend-select" | "--ignore" => {
+                let Some(value) = args_iter.next() else {
+                    usage(&args[0]);
+                };
+                let selectors = parse_selectors(value);
+                match arg.as_str() {
+                    "--select" => select.get_or_insert_with(Vec::new).extend(selectors),
+                    "--extend-select" => extend_select.extend(selectors),
+                    _ => ignore.extend(selectors),
+                }
+            }
+            "--list-rules" => {
+                list_rules();
+                return;
+            }
+            "--explain" => {
+                let Some(code) = args_iter.next() else {
+                    usage(&args[0]);
+                };
+                explain(code);
+                return;
+            }
+            "-h" | "--help" => {
+                println!("{}", usage_line(&args[0]));
+                return;
+            }
+            _ if arg.ends_with(".py") => files.push(Path::new(arg)),
+            _ if arg.starts_with('-') => {
+                eprintln!("unknown option '{}'", arg);
+                usage(&args[0]);
+            }
+            _ => {}
         }
+    }
 
-        if arg.ends_with(".py") {
-            let path = Path::new(arg);
+    // The opt-in flags select their rules, but --ignore still turns them off
+    config.rules = select_rules(select.as_deref(), &extend_select, &ignore);
+    config.require_zero_arg_super = config.rules.contains(&Rule::LegacySuper);
+    config.require_robust_teardown = config.rules.contains(&Rule::FragileTeardown);
 
-            // Fix the file if requested
-            if fix_mode {
-                let _ = fix_file(path);
-            }
+    // Fix the files if requested; the project is indexed again for checking, as fixing may
+    // have changed it
+    let outcome = if fix_mode {
+        fix_files_with_config(&files, &config)
+    } else {
+        FixOutcome::default()
+    };
+    for (path, err) in &outcome.errors {
+        eprintln!("{}: can't write fixed file: {}", path.display(), err);
+    }
+    let files_fixed = outcome.fixed;
 
-            // Check and collect any errors
-            errors.extend(check_file(path));
+    // Check all files together, so base classes in other files are resolved
+    let report = report_files_with_config(&files, &config);
+    let diagnostics = report.diagnostics;
+
+    // The machine-readable formats go to stdout, and describe clean runs too
+    let run = Run {
+        files_checked: &files,
+        files_fixed: &files_fixed,
+        diagnostics: &diagnostics,
+    };
+    match output_format {
+        OutputFormat::Concise => {
+            for diagnostic in &diagnostics {
+                eprintln!("{}", diagnostic);
+            }
         }
+        OutputFormat::Full => print_code_frames(&diagnostics),
+        OutputFormat::Json => println!("{}", json(&run)),
+        OutputFormat::JsonLines => print!("{}", json_lines(&run)),
+        OutputFormat::Sarif => println!("{}", sarif(&run)),
+        OutputFormat::Github => print!("{}", github(&run)),
+        OutputFormat::Gitlab => println!("{}", gitlab(&run)),
+        OutputFormat::Junit => print!("{}", junit(&run, &report.methods)),
     }
 
-    // Exit with error code if any violations were found
-    if !errors.is_empty() {
-        for error in &errors {
-            eprintln!("{}", error);
-        }
-        std::process::exit(1);
+    // Files that couldn't be checked or written back fail the run even with --exit-zero
+    if !outcome.errors.is_empty() || diagnostics.iter().any(|d| d.rule.is_load_error()) {
+        process::exit(EXIT_ERROR);
+    }
+    if exit_zero {
+        return;
     }
+    if (exit_non_zero_on_fix && !files_fixed.is_empty())
+        || diagnostics.iter().any(|d| !d.rule.is_load_error())
+    {
+        process::exit(EXIT_VIOLATIONS);
+    }
+}
+
+/// Print each diagnostic with the source lines it refers to.
+///
+/// Colors are used on a terminal, unless the `NO_COLOR` convention asks for none.
+fn print_code_frames(diagnostics: &[Diagnostic]) {
+    let no_color = env::var_os("NO_COLOR").is_some_and(|no_color| !no_color.is_empty());
+    let color = io::stderr().is_terminal() && !no_color;
+    let mut sources = HashMap::new();
+    for diagnostic in diagnostics {
+        let src = sources
+            .entry(&diagnostic.file)
+            .or_insert_with(|| fs::read_to_string(&diagnostic.file).unwrap_or_default());
+        eprintln!("{}", code_frame(diagnostic, src, color));
+    }
+}
+
+/// Parse a comma-separated list of rule selectors, exiting on an unknown one.
+fn parse_selectors(value: &str) -> Vec<RuleSelector> {
+    value
+        .split(',')
+        .filter(|selector| !selector.trim().is_empty())
+        .map(|selector| match selector.parse() {
+            Ok(selector) => selector,
+            Err(err) => {
+                eprintln!("{}", err);
+                process::exit(EXIT_USAGE);
+            }
+        })
+        .collect()
+}
+
+/// Print every rule's code, name, default enablement and description.
+fn list_rules() {
+    for info in RULES {
+        println!(
+            "{}  {:<26}  {:<7}  {}",
+            info.code,
+            info.name,
+            if info.default { "default" } else { "opt-in" },
+            info.description
+        );
+    }
+}
+
+/// Print a rule's description and explanation, exiting if the code is unknown.
+fn explain(code: &str) {
+    let Some(rule) = Rule::from_code(code) else {
+        eprintln!("unknown rule code '{}'", code);
+        process::exit(EXIT_USAGE);
+    };
+    let info = rule.info();
+    println!("{} ({})", info.code, info.name);
+    println!();
+    println!("{}.", info.description);
+    if !info.default {
+        println!();
+        println!("This rule is opt-in.");
+    }
+    println!();
+    println!("{}", info.explanation);
+}
+
+/// Print the usage line and exit with the usage error code.
+fn usage(program: &str) -> ! {
+    eprintln!("{}", usage_line(program));
+    process::exit(EXIT_USAGE);
+}
+
+/// Return the usage line.
+fn usage_line(program: &str) -> String {
+    format!(
+        "Usage: {} [--fix] [--exit-zero] [--exit-non-zero-on-fix] [--require-zero-arg-super] [--require-robust-teardown] [--super-position METHOD=first|last|anywhere-unconditional] [--test-case-root QUALIFIED.NAME] [--select CODES] [--extend-select CODES] [--ignore CODES] [--output-format concise|full|json|jsonl|sarif|github|gitlab|junit] [--list-rules] [--explain CODE] <file1.py> [file2.py] ...",
+        program
+    )
 }
@@ -0,0 +1,113 @@
+// src/mro.rs
+//! C3 linearization of project classes, the method resolution order Python uses.
+//!
+//! super() calls follow the MRO of the instance's class, not the bases a method's own class
+//! lists, so with mixins (`class T(DbMixin, CacheMixin, TestCase)`) only the MRO tells which
+//! `setUp` a `super().setUp()` reaches. Classes outside the project are opaque: they end up in
+//! the MRO by name, without their own ancestors.
+
+use crate::{
+    classes::Base,
+    index::{ClassRef, ProjectIndex},
+};
+
+/// A class in a method resolution order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum MroEntry<'a> {
+    /// A class defined in the project
+    Class(ClassRef<'a>),
+    /// A class imported from outside the project, by qualified name
+    Imported(String),
+    /// A name the module doesn't define or import, as written
+    Unknown(String),
+}
+
+impl MroEntry<'_> {
+    /// Return the class name, qualified for classes outside the project.
+    pub(crate) fn name(&self) -> &str {
+        match self {
+            MroEntry::Class(class) => class.class_def.name.as_str(),
+            MroEntry::Imported(name) | MroEntry::Unknown(name) => name,
+        }
+    }
+}
+
+/// Compute the MRO of a class, starting with the class itself.
+///
+/// Returns `None` if the bases admit no consistent order, for which Python raises a
+/// `TypeError`, or form a cycle. Bases that aren't class names, such as `make_base()`, are
+/// left out, and so is `object`, which every MRO ends with.
+pub(crate) fn linearize<'a>(
+    index: &ProjectIndex<'a>,
+    class: ClassRef<'a>,
+) -> Option<Vec<MroEntry<'a>>> {
+    linearize_class(index, class, &mut Vec::new())
+}
+
+fn linearize_class<'a>(
+    index: &ProjectIndex<'a>,
+    class: ClassRef<'a>,
+    stack: &mut Vec<ClassRef<'a>>,
+) -> Option<Vec<MroEntry<'a>>> {
+    // Guard against `class A(B)` / `class B(A)` cycles
+    if stack.contains(&class) {
+        return None;
+    }
+    stack.push(class);
+
+    let mut sequences = Vec::new();
+    let mut bases = Vec::new();
+    for base in index.bases(class) {
+        let base = match base {
+            Base::Class(base) => {
+                sequences.push(linearize_class(index, base, stack)?);
+                MroEntry::Class(base)
+            }
+            Base::Imported(name) if name == "builtins.object" => continue,
+            Base::Unknown(name) if name == "object" => continue,
+            Base::Imported(name) => {
+                sequences.push(vec![MroEntry::Imported(name.clone())]);
+                MroEntry::Imported(name)
+            }
+            Base::Unknown(name) => {
+                sequences.push(vec![MroEntry::Unknown(name.clone())]);
+                MroEntry::Unknown(name)
+            }
+        };
+        bases.push(base);
+    }
+    sequences.push(bases);
+    stack.pop();
+
+    let mut mro = vec![MroEntry::Class(class)];
+    mro.extend(merge(sequences)?);
+    Some(mro)
+}
+
+/// Merge the MROs of the bases and the list of bases, C3 style: repeatedly take the first head
+/// that appears in no sequence's tail.
+fn merge(mut sequences: Vec<Vec<MroEntry>>) -> Option<Vec<MroEntry>> {
+    let mut merged = Vec::new();
+    loop {
+        sequences.retain(|sequence| !sequence.is_empty());
+        if sequences.is_empty() {
+            return Some(merged);
+        }
+
+        let head = sequences
+            .iter()
+            .map(|sequence| &sequence[0])
+            .find(|head| {
+                sequences
+                    .iter()
+                    .all(|sequence| !sequence[1..].contains(head))
+            })?
+            .clone();
+        for sequence in &mut sequences {
+            if sequence[0] == head {
+                sequence.remove(0);
+            }
+        }
+        merged.push(head);
+    }
+}
@@ -0,0 +1,414 @@
+// src/output.rs
+//! Rendering diagnostics for the CLI.
+//!
+//! The `concise` format is one `path:line:column CODE message` line per violation, the
+//! [`Display`](std::fmt::Display) form of a [`Diagnostic`]. The `full` format shows the
+//! offending lines under each message, like a compiler: the violating code is underlined with
+//! carets, related code such as a misplaced super() call with dashes and its label, and the
+//! fix `--fix` would apply follows as a `help:` line.
+//!
+//! The `json` and `jsonl` formats are for tools: they follow a versioned schema,
+//! [`JSON_SCHEMA_VERSION`], that only changes in backwards-compatible ways (new fields) unless
+//! the version is bumped. `json` is one document with the run's metadata and every diagnostic;
+//! `jsonl` is one `"type": "diagnostic"` object per line, followed by a `"type": "run"` line
+//! with the metadata.
+//!
+//! The `sarif` format is a SARIF 2.1.0 log, for code scanning platforms. The `github` and
+//! `gitlab` formats annotate CI runs: GitHub Actions workflow commands and a GitLab Code
+//! Quality report. The `junit` format is a JUnit XML report with a test case per checked
+//! lifecycle method.
+
+use crate::{CheckedMethod, Diagnostic, Label, Location, Severity};
+use serde::Serialize;
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// How the CLI prints diagnostics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// One line per violation
+    #[default]
+    Concise,
+    /// Each violation with the source lines it refers to
+    Full,
+    /// One JSON document for the whole run
+    Json,
+    /// JSON Lines: one JSON object per diagnostic, then one for the run
+    JsonLines,
+    /// A SARIF 2.1.0 log
+    Sarif,
+    /// GitHub Actions workflow commands
+    Github,
+    /// A GitLab Code Quality report
+    Gitlab,
+    /// A JUnit XML report
+    Junit,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    /// Parse `concise`, `full`, `json`, `jsonl`, `sarif`, `github`, `gitlab` or `junit`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "concise" => Ok(OutputFormat::Concise),
+            "full" => Ok(OutputFormat::Full),
+            "json" => Ok(OutputFormat::Json),
+            "jsonl" => Ok(OutputFormat::JsonLines),
+            "sarif" => Ok(OutputFormat::Sarif),
+            "github" => Ok(OutputFormat::Github),
+            "gitlab" => Ok(OutputFormat::Gitlab),
+            "junit" => Ok(OutputFormat::Junit),
+            _ => Err(format!(
+                "invalid output format '{}': expected concise, full, json, jsonl, sarif, github, gitlab or junit",
+                s
+            )),
+        }
+    }
+}
+
+/// The version of the `json` and `jsonl` schema.
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+/// A run of the CLI, for the formats that report on it as a whole.
+pub struct Run<'a> {
+    /// The files given to check
+    pub files_checked: &'a [&'a Path],
+    /// The files `--fix` rewrote
+    pub files_fixed: &'a [PathBuf],
+    /// The violations found after fixing
+    pub diagnostics: &'a [Diagnostic],
+}
+
+/// The run's metadata in the JSON schema.
+#[derive(Serialize)]
+struct JsonRun<'a> {
+    schema_version: u32,
+    tool: JsonTool,
+    files_checked: &'a [&'a Path],
+    files_fixed: &'a [PathBuf],
+}
+
+#[derive(Serialize)]
+struct JsonTool {
+    name: &'static str,
+    version: &'static str,
+}
+
+/// A diagnostic in the JSON schema, kept apart from [`Diagnostic`]'s own serialization so
+/// the schema only changes with its version.
+#[derive(Serialize)]
+struct JsonDiagnostic<'a> {
+    code: &'static str,
+    name: &'static str,
+    severity: Severity,
+    message: &'a str,
+    file: &'a Path,
+    span: JsonSpan,
+    labels: Vec<JsonLabel<'a>>,
+    fixable: bool,
+    /// What the fix changes, if there is one
+    fix: Option<&'a str>,
+    class: Option<&'a str>,
+    method: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct JsonSpan {
+    start: Location,
+    end: Location,
+}
+
+#[derive(Serialize)]
+struct JsonLabel<'a> {
+    span: JsonSpan,
+    message: &'a str,
+}
+
+/// A line of the `jsonl` format.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum JsonLine<'a> {
+    Diagnostic(JsonDiagnostic<'a>),
+    Run(JsonRun<'a>),
+}
+
+impl<'a> JsonRun<'a> {
+    fn new(run: &Run<'a>) -> Self {
+        JsonRun {
+            schema_version: JSON_SCHEMA_VERSION,
+            tool: JsonTool {
+                name: env!("CARGO_PKG_NAME"),
+                version: env!("CARGO_PKG_VERSION"),
+            },
+            files_checked: run.files_checked,
+            files_fixed: run.files_fixed,
+        }
+    }
+}
+
+impl<'a> JsonDiagnostic<'a> {
+    fn new(diagnostic: &'a Diagnostic) -> Self {
+        // A fix without edits is one `--fix` turned out not to make, as in SARIF
+        let fix = diagnostic.fix.as_ref().filter(|fix| !fix.edits.is_empty());
+        JsonDiagnostic {
+            code: diagnostic.rule.code(),
+            name: diagnostic.rule.name(),
+            severity: diagnostic.severity,
+            message: &diagnostic.message,
+            file: &diagnostic.file,
+            span: JsonSpan {
+                start: diagnostic.start,
+                end: diagnostic.end,
+            },
+            labels: diagnostic.labels.iter().map(JsonLabel::new).collect(),
+            fixable: fix.is_some(),
+            fix: fix.map(|fix| fix.message.as_str()),
+            class: diagnostic.class.as_deref(),
+            method: diagnostic.method.as_deref(),
+        }
+    }
+}
+
+impl<'a> JsonLabel<'a> {
+    fn new(label: &'a Label) -> Self {
+        JsonLabel {
+            span: JsonSpan {
+                start: label.start,
+                end: label.end,
+            },
+            message: &label.message,
+        }
+    }
+}
+
+/// Render the run as one pretty-printed JSON document: the run's metadata, with the
+/// diagnostics under `diagnostics`.
+pub fn json(run: &Run) -> String {
+    #[derive(Serialize)]
+    struct JsonDocument<'a> {
+        #[serde(flatten)]
+        run: JsonRun<'a>,
+        diagnostics: Vec<JsonDiagnostic<'a>>,
+    }
+
+    let document = JsonDocument {
+        run: JsonRun::new(run),
+        diagnostics: run.diagnostics.iter().map(JsonDiagnostic::new).collect(),
+    };
+    // Paths that aren't UTF-8 are the only thing that can fail to serialize
+    serde_json::to_string_pretty(&document).unwrap_or_default()
+}
+
+/// Render the run as a SARIF 2.1.0 log.
+///
+/// Every rule is listed with its explanation as help text, and each fixable diagnostic
+/// carries the edits `--fix` makes as replacements.
+pub fn sarif(run: &Run) -> String {
+    crate::sarif::log(run)
+}
+
+/// Render the run as GitHub Actions workflow commands: an `::error` or `::warning` line per
+/// diagnostic, with its span and the rule as the title.
+pub fn github(run: &Run) -> String {
+    crate::ci::github(run)
+}
+
+/// Render the run as a GitLab Code Quality report.
+///
+/// Fingerprints are derived from the rule, file, class, method and message rather than the
+/// line, so a violation keeps its fingerprint when code above it changes.
+pub fn gitlab(run: &Run) -> String {
+    crate::ci::gitlab(run)
+}
+
+/// Render the run as a JUnit XML report: a test suite per file, and a test case per checked
+/// lifecycle method, from [`Report::methods`](crate::Report::methods), that fails with the
+/// method's violations.
+pub fn junit(run: &Run, methods: &[CheckedMethod]) -> String {
+    crate::junit::report(run, methods)
+}
+
+/// Render the run as JSON Lines: a `diagnostic` line per diagnostic, then a `run` line.
+pub fn json_lines(run: &Run) -> String {
+    run.diagnostics
+        .iter()
+        .map(|diagnostic| JsonLine::Diagnostic(JsonDiagnostic::new(diagnostic)))
+        .chain([JsonLine::Run(JsonRun::new(run))])
+        .filter_map(|line| serde_json::to_string(&line).ok())
+        .map(|line| line + "\n")
+        .collect()
+}
+
+/// Source lines between two annotated lines that are still shown rather than elided
+const MAX_GAP: usize = 3;
+
+/// An underlined span in a code frame.
+struct Annotation<'d> {
+    start: Location,
+    end: Location,
+    /// Whether this is the violation itself, rather than related code
+    primary: bool,
+    message: &'d str,
+}
+
+/// Render `diagnostic` as a code frame of `src`, the contents of its file.
+///
+/// With `color`, the frame is styled with ANSI escape codes. Lines outside `src` are left
+/// out, so a diagnostic for a file that changed since still renders its header.
+pub fn code_frame(diagnostic: &Diagnostic, src: &str, color: bool) -> String {
+    let style = Style { color };
+    let severity_style = match diagnostic.severity {
+        Severity::Error => RED,
+        Severity::Warning => YELLOW,
+    };
+    let severity = match diagnostic.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    };
+
+    let mut annotations = vec![Annotation {
+        start: diagnostic.start,
+        end: diagnostic.end,
+        primary: true,
+        message: "",
+    }];
+    annotations.extend(diagnostic.labels.iter().map(|label| Annotation {
+        start: label.start,
+        end: label.end,
+        primary: false,
+        message: &label.message,
+    }));
+    annotations.sort_by_key(|annotation| annotation.start);
+
+    let lines: Vec<&str> = src.lines().collect();
+    let mut shown: Vec<usize> = annotations
+        .iter()
+        .map(|annotation| annotation.start.line)
+        .filter(|&line| line <= lines.len())
+        .collect();
+    shown.dedup();
+    let width = shown.last().map_or(1, |line| line.to_string().len());
+    let gutter = |text: &str| style.paint(format!("{:>width$} |", text), BLUE);
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{}{}",
+        style.paint(
+            format!("{}[{}]", severity, diagnostic.rule.code()),
+            severity_style
+        ),
+        style.paint(format!(": {}", diagnostic.message), BOLD)
+    );
+    let _ = writeln!(
+        out,
+        "{}{} {}:{}:{}",
+        " ".repeat(width),
+        style.paint("-->", BLUE),
+        diagnostic.file.display(),
+        diagnostic.start.line,
+        diagnostic.start.column
+    );
+
+    if !shown.is_empty() {
+        let _ = writeln!(out, "{}", gutter(""));
+        let mut previous: Option<usize> = None;
+        for &line in &shown {
+            // Show short gaps between annotated lines, and elide longer ones
+            let first = match previous {
+                Some(previous) if line - previous - 1 <= MAX_GAP => previous + 1,
+                Some(_) => {
+                    let _ = writeln!(out, "{}", style.paint("...", BLUE));
+                    line
+                }
+                None => line,
+            };
+            for number in first..=line {
+                let _ = writeln!(out, "{} {}", gutter(&number.to_string()), lines[number - 1]);
+            }
+            previous = Some(line);
+
+            let text = lines[line - 1];
+            for annotation in annotations
+                .iter()
+                .filter(|annotation| annotation.start.line == line)
+            {
+                let (marker, marker_style) = if annotation.primary {
+                    ('^', severity_style)
+                } else {
+                    ('-', BLUE)
+                };
+                let mut underline = marker.to_string().repeat(underline_width(annotation, text));
+                if !annotation.message.is_empty() {
+                    underline = format!("{} {}", underline, annotation.message);
+                }
+                let _ = writeln!(
+                    out,
+                    "{} {}{}",
+                    gutter(""),
+                    indent(text, annotation.start.column),
+                    style.paint(underline, marker_style)
+                );
+            }
+        }
+        let _ = writeln!(out, "{}", gutter(""));
+    }
+
+    // A fix without edits is one `--fix` turned out not to make, so there's nothing to offer
+    if let Some(fix) = diagnostic.fix.as_ref().filter(|fix| !fix.edits.is_empty()) {
+        let _ = writeln!(
+            out,
+            "{} {} {}",
+            " ".repeat(width),
+            style.paint("= help:", BOLD),
+            fix.message
+        );
+    }
+    out
+}
+
+/// Return the whitespace that lines up an underline with `column` of `text`: tabs stay
+/// tabs, so the underline is aligned however wide the terminal draws them.
+fn indent(text: &str, column: usize) -> String {
+    text.chars()
+        .take(column - 1)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect()
+}
+
+/// Return how many characters to underline on the annotation's first line: a span that
+/// continues onto later lines is underlined to the end of its first.
+fn underline_width(annotation: &Annotation, text: &str) -> usize {
+    let end = if annotation.end.line == annotation.start.line {
+        annotation.end.column
+    } else {
+        text.chars().count() + 1
+    };
+    end.saturating_sub(annotation.start.column).max(1)
+}
+
+const BOLD: &str = "1";
+const RED: &str = "1;31";
+const YELLOW: &str = "1;33";
+const BLUE: &str = "1;34";
+
+/// ANSI styling that can be turned off.
+#[derive(Clone, Copy)]
+struct Style {
+    color: bool,
+}
+
+impl Style {
+    fn paint(self, text: impl Into<String>, code: &str) -> String {
+        let text = text.into();
+        if self.color {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text
+        }
+    }
+}
@@ -71,7 +71,8 @@ def main() -> int:
         binary_path = get_binary_path()
     except RuntimeError as e:
         print(f"Error: {e}", file=sys.stderr)
-        return 1
+        # The binary's exit code for errors, as opposed to violations (1) or usage errors (2)
+        return 3
 
     # Pass all arguments to the binary
     result = subprocess.run([str(binary_path)] + sys.argv[1:])
@@ -0,0 +1,40 @@
+// src/report.rs
+//! The outcome of checking a set of files.
+//!
+//! Besides the violations, a [`Report`] lists every lifecycle method that was checked, so
+//! that reporters can show the methods that pass as well as the ones that don't.
+
+use crate::{Diagnostic, Location};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// What checking a set of files found.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Report {
+    /// The violations of the enabled rules, in file order
+    pub diagnostics: Vec<Diagnostic>,
+    /// The lifecycle methods and hooks checked, in file order
+    pub methods: Vec<CheckedMethod>,
+}
+
+/// A lifecycle method or hook that was checked, whether or not it violates a rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CheckedMethod {
+    /// The file, as given or relative to the working directory
+    pub file: PathBuf,
+    /// The class the method is defined in, if any
+    pub class: Option<String>,
+    /// The method's name, as written
+    pub method: String,
+    /// Where the method's name is
+    pub start: Location,
+}
+
+impl CheckedMethod {
+    /// Return whether `diagnostic` is about this method.
+    pub fn is_reported_by(&self, diagnostic: &Diagnostic) -> bool {
+        diagnostic.file == self.file
+            && diagnostic.class == self.class
+            && diagnostic.method.as_deref() == Some(self.method.as_str())
+    }
+}
@@ -0,0 +1,551 @@
+// src/rules.rs
+//! The rules the checker reports violations of, and their registry.
+//!
+//! Every rule has a stable code (`PSH001`, ...) for selecting and ignoring it, a name, a
+//! one-line description, a longer explanation with examples for `--explain`, and whether it
+//! runs without opting in.
+
+use serde::{Serialize, Serializer};
+use std::{collections::HashSet, str::FromStr};
+
+/// A rule the checker reports violations of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rule {
+    /// A lifecycle method or module fixture spelled with the wrong case, e.g. `setup`
+    MethodCasing,
+    /// A lifecycle method that doesn't call super() where its position requires
+    SuperPosition,
+    /// A class-level fixture that isn't a `@classmethod`
+    MissingClassmethod,
+    /// `asyncSetUp` / `asyncTearDown` declared without `async def`
+    AsyncHookNotCoroutine,
+    /// A synchronous hook such as `setUp` declared with `async def`
+    SyncHookCoroutine,
+    /// `super().asyncSetUp()` without `await`
+    UnawaitedSuper,
+    /// `super(Cls, self).setUp()` or `Base.setUp(self)`, when opted into
+    LegacySuper,
+    /// A tearDown whose cleanup can raise before its super() call, when opted into
+    FragileTeardown,
+    /// `setUpModule` / `tearDownModule` defined inside a class
+    ModuleFixturePlacement,
+    /// `setUpModule` / `tearDownModule` taking `self` or other arguments
+    ModuleFixtureSignature,
+    /// `setUpModule` without `tearDownModule` or `addModuleCleanup`
+    MissingModuleTeardown,
+    /// A near-miss spelling of a pytest xunit hook, e.g. `setupMethod`
+    XunitSpelling,
+    /// A pytest xunit hook defined at the wrong level
+    XunitPlacement,
+    /// A pytest xunit hook pytest can't call with the arguments it passes
+    XunitSignature,
+    /// An ancestor's lifecycle method that ends the super() chain early
+    BrokenInheritedChain,
+    /// A mixin's lifecycle method the MRO never reaches
+    UnreachableMixinHook,
+    /// An attribute read before the ancestors' setUp assigns it
+    UseBeforeInit,
+    /// An attribute the ancestors' setUp assigns again after the subclass's setUp
+    AttributeOverwritten,
+    /// A file Python can't parse
+    SyntaxError,
+    /// A file that can't be read
+    IoError,
+    /// A file that isn't valid UTF-8
+    DecodeError,
+}
+
+impl Rule {
+    /// Return the rule's entry in [`RULES`].
+    pub fn info(self) -> &'static RuleInfo {
+        RULES.iter().find(|info| info.rule == self).unwrap()
+    }
+
+    /// Return the rule's stable code, e.g. `PSH001`.
+    pub fn code(self) -> &'static str {
+        self.info().code
+    }
+
+    /// Return the rule's name, e.g. `method-casing`.
+    pub fn name(self) -> &'static str {
+        self.info().name
+    }
+
+    /// Return the rule with the given code, ignoring case.
+    pub fn from_code(code: &str) -> Option<Rule> {
+        RULES
+            .iter()
+            .find(|info| info.code.eq_ignore_ascii_case(code))
+            .map(|info| info.rule)
+    }
+
+    /// Return whether the rule reports a file that couldn't be read, decoded or parsed, rather
+    /// than a violation in its code.
+    pub fn is_load_error(self) -> bool {
+        matches!(self, Rule::SyntaxError | Rule::IoError | Rule::DecodeError)
+    }
+
+    /// Return how serious a violation of the rule is.
+    pub fn severity(self) -> Severity {
+        match self {
+            Rule::MissingModuleTeardown => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+/// Rules are identified by their code in serialized output.
+impl Serialize for Rule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+/// How serious a violation is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Code that doesn't do what it looks like it does
+    Error,
+    /// Code that works, but likely leaks state
+    Warning,
+}
+
+/// A rule's entry in the registry.
+#[derive(Debug)]
+pub struct RuleInfo {
+    pub rule: Rule,
+    /// The stable code, e.g. `PSH001`
+    pub code: &'static str,
+    /// The kebab-case name, e.g. `method-casing`
+    pub name: &'static str,
+    /// What the rule flags, in one line
+    pub description: &'static str,
+    /// Why the rule matters, with an example, for `--explain`
+    pub explanation: &'static str,
+    /// Whether the rule runs without being selected
+    pub default: bool,
+}
+
+/// Every rule, in code order.
+pub static RULES: &[RuleInfo] = &[
+    RuleInfo {
+        rule: Rule::MethodCasing,
+        code: "PSH001",
+        name: "method-casing",
+        description: "Lifecycle methods and module fixtures spelled with the wrong case",
+        explanation: r#"unittest only calls hooks spelled exactly `setUp`, `tearDown`, `setUpClass`,
+`tearDownClass`, `setUpTestData`, `asyncSetUp`, `asyncTearDown`, `setUpModule` and
+`tearDownModule`. A method named `setup` is an ordinary method that never runs.
+
+    class TestApi(TestCase):
+        def setup(self):  # never called
+            self.client = Client()
+
+Use instead:
+
+    class TestApi(TestCase):
+        def setUp(self):
+            self.client = Client()
+            super().setUp()
+
+`--fix` renames the method."#,
+        default: true,
+    },
+    RuleInfo {
+        rule: Rule::SuperPosition,
+        code: "PSH002",
+        name: "super-position",
+        description: "Lifecycle methods that don't call super() where their position requires",
+        explanation: r#"A lifecycle method that doesn't call its parent's skips the parent's setup or
+cleanup. By default the call has to be the last statement, on every path through the method;
+`--super-position METHOD=first|last|anywhere-unconditional` changes that per method.
+
+    class TestApi(ApiTestCase):
+        def setUp(self):
+            if self.fast:
+                return  # ApiTestCase.setUp never runs
+            self.client = Client()
+            super().setUp()
+
+`--fix` adds or moves the call, unless it sits inside a branch or the method returns
+early."#,
+        default: true,
+    },
+    RuleInfo {
+        rule: Rule::MissingClassmethod,
+        code: "PSH003",
+        name: "missing-classmethod",
+        description: "setUpClass, tearDownClass and setUpTestData without @classmethod",
+        explanation: r#"unittest calls class-level fixtures on the class, so they have to be
+classmethods; without the decorator the call fails, or `cls` silently is something else.
+
+    class TestApi(TestCase):
+        def setUpClass(cls):
+            super().setUpClass()
+
+Use instead:
+
+    class TestApi(TestCase):
+        @classmethod
+        def setUpClass(cls):
+            super().setUpClass()
+
+`--fix` adds the decorator."#,
+        default: true,
+    },
+    RuleInfo {
+        rule: Rule::AsyncHookNotCoroutine,
+        code: "PSH004",
+        name: "async-hook-not-coroutine",
+        description: "asyncSetUp and asyncTearDown declared without async def",
+        explanation: r#"IsolatedAsyncioTestCase awaits `asyncSetUp` and `asyncTearDown`, which fails
+for a plain function.
+
+    class TestApi(IsolatedAsyncioTestCase):
+        def asyncSetUp(self):
+            await super().asyncSetUp()
+
+Use `async def asyncSetUp(self):` instead. `--fix` adds `async`."#,
+        default: true,
+    },
+    RuleInfo {
+        rule: Rule::SyncHookCoroutine,
+        code: "PSH005",
+        name: "sync-hook-coroutine",
+        description: "Synchronous hooks such as setUp declared with async def",
+        explanation: r#"unittest calls `setUp` and the other synchronous hooks without awaiting them,
+so an `async def setUp` only creates a coroutine and its body never runs.
+
+    class TestApi(IsolatedAsyncioTestCase):
+        async def setUp(self):
+            self.client = await connect()
+
+Use `async def asyncSetUp(self):` instead."#,
+        default: true,
+    },
+    RuleInfo {
+        rule: Rule::UnawaitedSuper,
+        code: "PSH006",
+        name: "unawaited-super",
+        description: "super().asyncSetUp() and super().asyncTearDown() without await",
+        explanation: r#"Calling a coroutine function without awaiting it only creates the coroutine,
+so the parent's hook never runs.
+
+    class TestApi(IsolatedAsyncioTestCase):
+        async def asyncSetUp(self):
+            self.client = await connect()
+            super().asyncSetUp()
+
+Use `await super().asyncSetUp()` instead. `--fix` adds the `await`."#,
+        default: true,
+    },
+    RuleInfo {
+        rule: Rule::LegacySuper,
+        code: "PSH007",
+        name: "legacy-super",
+        description: "super(Cls, self).setUp() and Base.setUp(self) instead of super().setUp()",
+        explanation: r#"The legacy spellings chain to the parent, but break silently when the class
+is renamed or its bases change, and `Base.setUp(self)` skips the rest of the MRO. A `super()`
+naming a class other than the enclosing one is reported as such.
+
+    class TestApi(ApiTestCase):
+        def setUp(self):
+            self.client = Client()
+            super(TestApi, self).setUp()
+
+Use `super().setUp()` instead. Opt in with `--require-zero-arg-super` or `--extend-select
+PSH007`; `--fix` rewrites calls naming the enclosing class or its first base."#,
+        default: false,
+    },
+    RuleInfo {
+        rule: Rule::FragileTeardown,
+        code: "PSH008",
+        name: "fragile-teardown",
+        description: "tearDowns whose cleanup can raise before an unprotected super() call",
+        explanation: r#"When cleanup before the super() call raises, the parent's cleanup never runs
+and its resources leak into the following tests.
+
+    class TestApi(ApiTestCase):
+        def tearDown(self):
+            self.client.close()
+            super().tearDown()
+
+Use instead:
+
+    class TestApi(ApiTestCase):
+        def tearDown(self):
+            try:
+                self.client.close()
+            finally:
+                super().tearDown()
+
+Opt in with `--require-robust-teardown` or `--extend-select PSH008`; `--fix` wraps the
+cleanup."#,
+        default: false,
+    },
+    RuleInfo {
+        rule: Rule::ModuleFixturePlacement,
+        code: "PSH009",
+        name: "module-fixture-placement",
+        description: "setUpModule and tearDownModule defined inside a class",
+        explanation: r#"unittest only looks up `setUpModule` and `tearDownModule` at module level; as
+methods they never run.
+
+    class TestApi(TestCase):
+        def setUpModule():
+            start_server()
+
+Define the function at the top level of the module instead."#,
+        default: true,
+    },
+    RuleInfo {
+        rule: Rule::ModuleFixtureSignature,
+        code: "PSH010",
+        name: "module-fixture-signature",
+        description: "setUpModule and tearDownModule taking self or other arguments",
+        explanation: r#"unittest calls module fixtures without arguments, so any required parameter
+makes the call fail.
+
+    def setUpModule(self):
+        start_server()
+
+Use `def setUpModule():` instead."#,
+        default: true,
+    },
+    RuleInfo {
+        rule: Rule::MissingModuleTeardown,
+        code: "PSH011",
+        name: "missing-module-teardown",
+        description: "setUpModule without tearDownModule or addModuleCleanup",
+        explanation: r#"Whatever `setUpModule` creates outlives the module's tests unless a
+`tearDownModule` or an `addModuleCleanup` registration removes it.
+
+    def setUpModule():
+        start_server()
+
+Use instead:
+
+    def setUpModule():
+        start_server()
+        unittest.addModuleCleanup(stop_server)"#,
+        default: true,
+    },
+    RuleInfo {
+        rule: Rule::XunitSpelling,
+        code: "PSH012",
+        name: "xunit-spelling",
+        description: "Near-miss spellings of pytest xunit hooks, such as setupMethod",
+        explanation: r#"pytest only calls xunit-style hooks spelled exactly `setup_method`,
+`teardown_method`, `setup_class`, `teardown_class`, `setup_module`, `teardown_module`,
+`setup_function` and `teardown_function`.
+
+    class TestApi:
+        def setupMethod(self, method):  # never called
+            self.client = Client()
+
+Use `def setup_method(self, method):` instead. `--fix` renames the function."#,
+        default: true,
+    },
+    RuleInfo {
+        rule: Rule::XunitPlacement,
+        code: "PSH013",
+        name: "xunit-placement",
+        description: "pytest xunit hooks defined at the wrong level",
+        explanation: r#"pytest looks up `setup_method` and `setup_class` (and their teardowns) in test
+classes, and `setup_module` and `setup_function` at module level; elsewhere they never run.
+
+    def setup_method(method):
+        connect()
+
+Move the hook into the test class, or use `setup_function` instead."#,
+        default: true,
+    },
+    RuleInfo {
+        rule: Rule::XunitSignature,
+        code: "PSH014",
+        name: "xunit-signature",
+        description: "pytest xunit hooks pytest can't call with the arguments it passes",
+        explanation: r#"pytest passes xunit hooks a fixed set of arguments, e.g. the test method to
+`setup_method(self, method)`; a hook requiring more fails when called.
+
+    class TestApi:
+        def setup_method(self, method, client):
+            self.client = client
+
+Give extra parameters defaults, or use a fixture instead."#,
+        default: true,
+    },
+    RuleInfo {
+        rule: Rule::BrokenInheritedChain,
+        code: "PSH015",
+        name: "broken-inherited-chain",
+        description: "Ancestors whose lifecycle methods end the super() chain early",
+        explanation: r#"When a base class's `setUp` doesn't call super(), the classes after it in the
+MRO, including the TestCase itself, never set up, in every test class deriving from it. The
+break is reported on each leaf test class, with the chain leading to it.
+
+    class ApiBase(TestCase):
+        def setUp(self):
+            self.client = Client()
+
+    class TestApi(ApiBase):  # TestCase.setUp never runs
+        pass
+
+Call `super().setUp()` at the end of `ApiBase.setUp`."#,
+        default: true,
+    },
+    RuleInfo {
+        rule: Rule::UnreachableMixinHook,
+        code: "PSH016",
+        name: "unreachable-mixin-hook",
+        description: "Mixin lifecycle methods the MRO places after the TestCase",
+        explanation: r#"unittest's `TestCase.setUp` doesn't call super(), so a mixin listed after the
+TestCase in the bases never sets up.
+
+    class DbMixin:
+        def setUp(self):
+            self.db = connect()
+            super().setUp()
+
+    class TestDb(TestCase, DbMixin):  # DbMixin.setUp never runs
+        pass
+
+List the mixin first: `class TestDb(DbMixin, TestCase)`."#,
+        default: true,
+    },
+    RuleInfo {
+        rule: Rule::UseBeforeInit,
+        code: "PSH017",
+        name: "use-before-init",
+        description: "Attributes read before the ancestors' setUp assigns them",
+        explanation: r#"With super().setUp() called last, a subclass's setUp runs before its
+ancestors'; reading an attribute only they assign raises AttributeError.
+
+    class ApiBase(TestCase):
+        def setUp(self):
+            self.client = Client()
+            super().setUp()
+
+    class TestApi(ApiBase):
+        def setUp(self):
+            self.response = self.client.get("/")  # AttributeError
+            super().setUp()
+
+Move the read after the super() call, or use `--super-position setUp=first`."#,
+        default: true,
+    },
+    RuleInfo {
+        rule: Rule::AttributeOverwritten,
+        code: "PSH018",
+        name: "attribute-overwritten",
+        description: "Attributes the ancestors' setUp assigns again after the subclass's setUp",
+        explanation: r#"With super().setUp() called last, an attribute the subclass assigns before the
+call is silently replaced when an ancestor's setUp assigns it too.
+
+    class ApiBase(TestCase):
+        def setUp(self):
+            self.user = make_user()
+            super().setUp()
+
+    class TestAdmin(ApiBase):
+        def setUp(self):
+            self.user = make_admin()  # replaced by make_user()
+            super().setUp()
+
+Assign the attribute after the super() call, or have the ancestor keep an existing value
+(`if not hasattr(self, "user"):`)."#,
+        default: true,
+    },
+    RuleInfo {
+        rule: Rule::SyntaxError,
+        code: "PSH019",
+        name: "syntax-error",
+        description: "Files with syntax errors",
+        explanation: r#"A file Python can't parse fails when pytest imports it, so none of its tests run.
+
+    class TestWidget(TestCase):
+        def setUp(self)  # missing colon
+            super().setUp()
+
+The parser recovers from the error, so the rest of the file is still checked, but `--fix`
+leaves files with syntax errors alone. Fix the syntax error first."#,
+        default: true,
+    },
+    RuleInfo {
+        rule: Rule::IoError,
+        code: "PSH020",
+        name: "io-error",
+        description: "Files that can't be read",
+        explanation: r#"A file given to check that doesn't exist, is a directory, or can't be read for lack of
+permission. Nothing in it is checked, so it is reported rather than passed."#,
+        default: true,
+    },
+    RuleInfo {
+        rule: Rule::DecodeError,
+        code: "PSH021",
+        name: "decode-error",
+        description: "Files that aren't valid UTF-8",
+        explanation: r#"Python source is UTF-8 unless a coding declaration says otherwise, and only UTF-8 is
+supported. A file saved as Latin-1 or another legacy encoding is reported at its first byte
+that isn't valid UTF-8.
+
+The rest of the file is still checked, with the undecodable bytes replaced, but `--fix`
+leaves it alone so they aren't lost. Convert the file to UTF-8."#,
+        default: true,
+    },
+];
+
+/// A `--select` / `--ignore` argument: a code prefix such as `PSH0` or `PSH001`, or `ALL`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleSelector(String);
+
+impl RuleSelector {
+    /// Check if the selector selects `rule`.
+    pub fn matches(&self, rule: Rule) -> bool {
+        self.0 == "ALL" || rule.code().starts_with(&self.0)
+    }
+}
+
+impl From<Rule> for RuleSelector {
+    /// Select exactly `rule`.
+    fn from(rule: Rule) -> Self {
+        RuleSelector(rule.code().to_string())
+    }
+}
+
+impl FromStr for RuleSelector {
+    type Err = String;
+
+    /// Parse a code prefix, ignoring case; it has to select at least one rule.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let selector = RuleSelector(s.trim().to_ascii_uppercase());
+        if selector.0.is_empty() || !RULES.iter().any(|info| selector.matches(info.rule)) {
+            return Err(format!("unknown rule selector '{}'", s));
+        }
+        Ok(selector)
+    }
+}
+
+/// Resolve the enabled rules from `--select`, `--extend-select` and `--ignore`.
+///
+/// `select` replaces the default rules, `extend_select` adds to them, and `ignore` removes
+/// rules from the result, however specific the selectors are. Files that can't be loaded
+/// are reported whatever `select` says (see [`Rule::is_load_error`]); only `ignore` drops
+/// them.
+pub fn select_rules(
+    select: Option<&[RuleSelector]>,
+    extend_select: &[RuleSelector],
+    ignore: &[RuleSelector],
+) -> HashSet<Rule> {
+    RULES
+        .iter()
+        .map(|info| info.rule)
+        .filter(|&rule| match select {
+            Some(select) => select.iter().any(|selector| selector.matches(rule)),
+            None => rule.info().default,
+        } || rule.is_load_error()
+            || extend_select.iter().any(|selector| selector.matches(rule)))
+        .filter(|&rule| !ignore.iter().any(|selector| selector.matches(rule)))
+        .collect()
+}
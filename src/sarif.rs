@@ -0,0 +1,132 @@
+// src/sarif.rs
+//! SARIF 2.1.0 output, for code scanning platforms.
+//!
+//! The log has a single run: the tool driver lists every rule with its description and
+//! explanation as help text, and each diagnostic becomes a result with its physical location,
+//! its labels as related locations, and the rewrite `--fix` makes as a fix with replacement
+//! edits. Columns count Unicode code points, like [`Location`]'s.
+
+use crate::{output::Run, Diagnostic, Location, Severity, RULES};
+use serde_json::{json, Value};
+use std::path::Path;
+
+/// Render the run as a SARIF log.
+pub(crate) fn log(run: &Run) -> String {
+    let rules: Vec<Value> = RULES
+        .iter()
+        .map(|info| {
+            json!({
+                "id": info.code,
+                "name": info.name,
+                "shortDescription": {"text": info.description},
+                "fullDescription": {"text": info.description},
+                "help": {"text": info.explanation},
+                "defaultConfiguration": {
+                    "enabled": info.default,
+                    "level": level(info.rule.severity()),
+                },
+            })
+        })
+        .collect();
+
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/clintonsteiner/pytest-super-setup-hooks",
+                    "rules": rules,
+                },
+            },
+            "columnKind": "unicodeCodePoints",
+            "results": run.diagnostics.iter().map(result).collect::<Vec<_>>(),
+        }],
+    });
+    // Paths that aren't UTF-8 are the only thing that can fail to serialize
+    serde_json::to_string_pretty(&log).unwrap_or_default()
+}
+
+/// Return the SARIF result for a diagnostic.
+fn result(diagnostic: &Diagnostic) -> Value {
+    let uri = uri(&diagnostic.file);
+    let mut result = json!({
+        "ruleId": diagnostic.rule.code(),
+        "ruleIndex": RULES.iter().position(|info| info.rule == diagnostic.rule),
+        "level": level(diagnostic.severity),
+        "message": {"text": diagnostic.message},
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": {"uri": uri},
+                "region": region(diagnostic.start, diagnostic.end),
+            },
+        }],
+    });
+    if !diagnostic.labels.is_empty() {
+        result["relatedLocations"] = diagnostic
+            .labels
+            .iter()
+            .enumerate()
+            .map(|(id, label)| {
+                json!({
+                    "id": id,
+                    "message": {"text": label.message},
+                    "physicalLocation": {
+                        "artifactLocation": {"uri": uri},
+                        "region": region(label.start, label.end),
+                    },
+                })
+            })
+            .collect();
+    }
+    // A fix without edits is one `--fix` turned out not to make, e.g. for a one-line method
+    if let Some(fix) = diagnostic.fix.as_ref().filter(|fix| !fix.edits.is_empty()) {
+        result["fixes"] = json!([{
+            "description": {"text": fix.message},
+            "artifactChanges": [{
+                "artifactLocation": {"uri": uri},
+                "replacements": fix
+                    .edits
+                    .iter()
+                    .map(|edit| {
+                        json!({
+                            "deletedRegion": region(edit.start, edit.end),
+                            "insertedContent": {"text": edit.content},
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+            }],
+        }]);
+    }
+    result
+}
+
+/// Return the SARIF region spanning `start` to `end`, exclusive.
+fn region(start: Location, end: Location) -> Value {
+    json!({
+        "startLine": start.line,
+        "startColumn": start.column,
+        "endLine": end.line,
+        "endColumn": end.column,
+    })
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    }
+}
+
+/// Return the URI of `path`: relative paths stay relative to the working directory, which
+/// code scanning platforms resolve against the repository.
+fn uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    if path.starts_with('/') {
+        format!("file://{}", path)
+    } else {
+        path.trim_start_matches("./").to_string()
+    }
+}
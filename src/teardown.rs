@@ -0,0 +1,159 @@
+// src/teardown.rs
+//! The opt-in tearDown robustness rule.
+//!
+//! A super() call at the end of `tearDown` is skipped as soon as the cleanup before it raises,
+//! and then the parent's cleanup leaks too. A robust tearDown either has nothing before the
+//! call that can raise, or makes the call from a `finally:` block.
+
+use crate::{is_async_hook, snippet, super_call, Checker, Fix, Rule};
+use ruff_python_ast::visitor::{walk_expr, walk_stmt, Visitor};
+use ruff_python_ast::{ExceptHandler, Expr, Stmt, StmtFunctionDef, StmtTry};
+
+/// Check if `name` is a teardown hook the robustness rule applies to.
+pub(crate) fn is_teardown(name: &str) -> bool {
+    matches!(name, "tearDown" | "tearDownClass" | "asyncTearDown")
+}
+
+/// Flag a teardown whose cleanup can raise before its unprotected super() call, at the first
+/// statement that can raise.
+///
+/// `fixable` tells whether `--fix` can wrap the cleanup in `try:` / `finally:`.
+pub(crate) fn check_robust_teardown(
+    checker: &mut Checker,
+    func_def: &StmtFunctionDef,
+    fixable: bool,
+) {
+    let expected = func_def.name.as_str();
+    let Some(cleanup) = unprotected_cleanup(&func_def.body, expected) else {
+        return;
+    };
+
+    let call = format!(
+        "{}super().{}()",
+        if is_async_hook(expected) {
+            "await "
+        } else {
+            ""
+        },
+        expected
+    );
+    let src = checker.src;
+    let line = checker.line_index.line_number(cleanup);
+    let label = func_def
+        .body
+        .iter()
+        .find_map(|stmt| super_call(stmt, expected))
+        .map(|super_call| {
+            checker.label(
+                super_call.call,
+                format_args!("{} found here", snippet(src, super_call.call)),
+            )
+        });
+    let diagnostic = checker.report(
+        Rule::FragileTeardown,
+        cleanup,
+        format_args!(
+            "{} is skipped if `{}` (line {}) raises; call it in a `finally:` block",
+            call,
+            snippet(src, cleanup),
+            line
+        ),
+    );
+    diagnostic.labels.extend(label);
+    if fixable {
+        diagnostic.fix = Some(Fix::new(format_args!(
+            "Wrap the cleanup in try: and call {} in finally:",
+            call
+        )));
+    }
+}
+
+/// Return the first statement that can raise before the super() call to `expected`.
+///
+/// Only a call that is a top-level statement of the method is unprotected; one inside a
+/// `finally:` block runs regardless, and one inside another branch is the control-flow check's
+/// business. A method without any call is treated as if the call were missing from its end.
+pub(crate) fn unprotected_cleanup<'a>(body: &'a [Stmt], expected: &str) -> Option<&'a Stmt> {
+    let end = match body
+        .iter()
+        .position(|stmt| super_call(stmt, expected).is_some())
+    {
+        Some(index) => index,
+        None if contains_super_call(body, expected) => return None,
+        None => body.len(),
+    };
+    body[..end].iter().find(|stmt| may_raise(stmt))
+}
+
+/// Check if a statement can raise: anything that calls, awaits, deletes, asserts or raises.
+///
+/// A `try` with a handler catching every exception only raises from its `else:` and
+/// `finally:` blocks.
+fn may_raise(stmt: &Stmt) -> bool {
+    let mut finder = RaiseFinder { found: false };
+    finder.visit_stmt(stmt);
+    finder.found
+}
+
+/// Finds code that can raise, without looking into nested function or class bodies.
+struct RaiseFinder {
+    found: bool,
+}
+
+impl<'a> Visitor<'a> for RaiseFinder {
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        match stmt {
+            Stmt::FunctionDef(_) | Stmt::ClassDef(_) => {}
+            Stmt::Delete(_) | Stmt::Assert(_) | Stmt::Raise(_) => self.found = true,
+            Stmt::Try(try_stmt) if catches_everything(try_stmt) => {
+                self.visit_body(&try_stmt.orelse);
+                self.visit_body(&try_stmt.finalbody);
+            }
+            _ => walk_stmt(self, stmt),
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        match expr {
+            Expr::Call(_) | Expr::Await(_) => self.found = true,
+            _ => walk_expr(self, expr),
+        }
+    }
+}
+
+/// Check if a `try` has a bare `except`, `except Exception` or `except BaseException` handler.
+fn catches_everything(try_stmt: &StmtTry) -> bool {
+    try_stmt.handlers.iter().any(|handler| {
+        let ExceptHandler::ExceptHandler(handler) = handler;
+        match handler.type_.as_deref() {
+            None => true,
+            Some(Expr::Name(name)) => matches!(name.id.as_str(), "Exception" | "BaseException"),
+            Some(_) => false,
+        }
+    })
+}
+
+/// Check if `body` calls super().`expected`() anywhere, including nested blocks.
+pub(crate) fn contains_super_call(body: &[Stmt], expected: &str) -> bool {
+    struct SuperCallFinder<'e> {
+        expected: &'e str,
+        found: bool,
+    }
+
+    impl<'a> Visitor<'a> for SuperCallFinder<'_> {
+        fn visit_stmt(&mut self, stmt: &'a Stmt) {
+            if super_call(stmt, self.expected).is_some() {
+                self.found = true;
+            } else {
+                walk_stmt(self, stmt);
+            }
+        }
+    }
+
+    let mut finder = SuperCallFinder {
+        expected,
+        found: false,
+    };
+    finder.visit_body(body);
+    finder.found
+}
@@ -0,0 +1,178 @@
+// src/xunit.rs
+//! Checks for pytest's xunit-style setup/teardown hooks.
+//!
+//! Plain pytest test classes and modules use `setup_method` / `teardown_method` /
+//! `setup_class` / `teardown_class` and `setup_module` / `teardown_module` /
+//! `setup_function` / `teardown_function` instead of unittest's camelCase methods. pytest
+//! only calls them when they are spelled exactly right, defined at the right level, and
+//! accept the arguments it passes.
+
+use crate::{normalize_name, Checker, Fix, Rule, Scope};
+use ruff_python_ast::{Stmt, StmtFunctionDef};
+use ruff_text_size::Ranged;
+
+/// A pytest xunit-style hook.
+struct XunitHook {
+    /// The name pytest looks up
+    name: &'static str,
+    /// Where the hook has to be defined for pytest to find it
+    scope: Scope,
+    /// The parameters pytest passes, in order
+    params: &'static [&'static str],
+}
+
+impl XunitHook {
+    /// The number of leading parameters the hook can't do without.
+    ///
+    /// pytest passes the trailing `method` / `module` / `function` argument only if the hook
+    /// accepts it, but a method hook still needs `self`.
+    fn required_params(&self) -> usize {
+        usize::from(self.params.first() == Some(&"self"))
+    }
+}
+
+const XUNIT_HOOKS: &[XunitHook] = &[
+    XunitHook {
+        name: "setup_method",
+        scope: Scope::Class,
+        params: &["self", "method"],
+    },
+    XunitHook {
+        name: "teardown_method",
+        scope: Scope::Class,
+        params: &["self", "method"],
+    },
+    XunitHook {
+        name: "setup_class",
+        scope: Scope::Class,
+        params: &["cls"],
+    },
+    XunitHook {
+        name: "teardown_class",
+        scope: Scope::Class,
+        params: &["cls"],
+    },
+    XunitHook {
+        name: "setup_module",
+        scope: Scope::Module,
+        params: &["module"],
+    },
+    XunitHook {
+        name: "teardown_module",
+        scope: Scope::Module,
+        params: &["module"],
+    },
+    XunitHook {
+        name: "setup_function",
+        scope: Scope::Module,
+        params: &["function"],
+    },
+    XunitHook {
+        name: "teardown_function",
+        scope: Scope::Module,
+        params: &["function"],
+    },
+];
+
+/// unittest hooks whose names collapse onto an xunit hook name when case and underscores are
+/// ignored; they are checked by the unittest rules instead.
+const UNITTEST_LOOKALIKES: &[&str] = &[
+    "setUpClass",
+    "tearDownClass",
+    "setUpModule",
+    "tearDownModule",
+];
+
+/// Check a function definition against the pytest xunit hooks.
+///
+/// Exact hook names are checked for placement and signature; near-miss spellings such as
+/// `setupMethod` or `teardown_Class` are flagged with the intended name.
+pub(crate) fn check_xunit_hook(
+    checker: &mut Checker,
+    stmt: &Stmt,
+    func_def: &StmtFunctionDef,
+    scope: Scope,
+) {
+    let name = func_def.name.as_str();
+
+    let Some(hook) = XUNIT_HOOKS.iter().find(|hook| hook.name == name) else {
+        if let Some(canonical) = near_miss(name) {
+            checker.checked(func_def);
+            checker
+                .report(
+                    Rule::XunitSpelling,
+                    func_def.name.range(),
+                    format_args!("use correct spelling: {}", canonical),
+                )
+                .fix = Some(Fix::new(format_args!("Rename to {}", canonical)));
+        }
+        return;
+    };
+
+    checker.checked(func_def);
+    if hook.scope != scope {
+        checker.report(
+            Rule::XunitPlacement,
+            stmt,
+            format_args!(
+                "{} must be defined {}",
+                hook.name,
+                match hook.scope {
+                    Scope::Class => "inside a test class",
+                    Scope::Module => "at module level",
+                }
+            ),
+        );
+    }
+
+    if !has_valid_signature(hook, func_def) {
+        checker.report(
+            Rule::XunitSignature,
+            stmt,
+            format_args!(
+                "{} must have the signature {}({})",
+                hook.name,
+                hook.name,
+                hook.params.join(", ")
+            ),
+        );
+    }
+}
+
+/// Return the xunit hook that `name` is a near-miss spelling of, if any.
+///
+/// A near miss matches a hook name once case and underscores are ignored, e.g. `setupMethod`
+/// for `setup_method`. unittest's own camelCase hooks are never near misses.
+pub(crate) fn near_miss(name: &str) -> Option<&'static str> {
+    if UNITTEST_LOOKALIKES.contains(&name) {
+        return None;
+    }
+
+    let normalized = normalize_name(name);
+    XUNIT_HOOKS
+        .iter()
+        .find(|hook| hook.name != name && normalize_name(hook.name) == normalized)
+        .map(|hook| hook.name)
+}
+
+/// Check that pytest can call the hook with the arguments it passes.
+fn has_valid_signature(hook: &XunitHook, func_def: &StmtFunctionDef) -> bool {
+    let parameters = &func_def.parameters;
+    let positional: Vec<_> = parameters
+        .posonlyargs
+        .iter()
+        .chain(&parameters.args)
+        .collect();
+
+    // Anything pytest doesn't pass must have a default
+    let extra_required = positional
+        .iter()
+        .skip(hook.params.len())
+        .any(|param| param.default.is_none());
+    let required_kwonly = parameters
+        .kwonlyargs
+        .iter()
+        .any(|param| param.default.is_none());
+
+    positional.len() >= hook.required_params() && !extra_required && !required_kwonly
+}
@@ -0,0 +1,134 @@
+// tests/async_hooks.rs
+use pytest_super_hooks::check_file;
+use std::fs::write;
+use tempfile::TempDir;
+
+// Test suite for IsolatedAsyncioTestCase hooks (asyncSetUp / asyncTearDown)
+
+fn run(src: &str) -> Vec<String> {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    check_file(&file_path)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn async_hooks_valid() {
+    let src = r#"
+class AsyncTest(IsolatedAsyncioTestCase):
+    async def asyncSetUp(self):
+        self.client = await make_client()
+        await super().asyncSetUp()
+
+    async def asyncTearDown(self):
+        await self.client.close()
+        await super().asyncTearDown()
+"#;
+    assert!(run(src).is_empty());
+}
+
+#[test]
+fn async_setup_super_not_awaited() {
+    let src = r#"
+class AsyncTest(IsolatedAsyncioTestCase):
+    async def asyncSetUp(self):
+        self.client = await make_client()
+        super().asyncSetUp()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("super().asyncSetUp() must be awaited"));
+}
+
+#[test]
+fn async_teardown_missing_super() {
+    let src = r#"
+class AsyncTest(IsolatedAsyncioTestCase):
+    async def asyncTearDown(self):
+        await self.client.close()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("await super().asyncTearDown() must be the last line"));
+}
+
+#[test]
+fn async_setup_super_not_last() {
+    let src = r#"
+class AsyncTest(IsolatedAsyncioTestCase):
+    async def asyncSetUp(self):
+        await super().asyncSetUp()
+        self.client = await make_client()
+"#;
+    assert_eq!(run(src).len(), 1);
+}
+
+#[test]
+fn async_setup_declared_sync() {
+    let src = r#"
+class AsyncTest(IsolatedAsyncioTestCase):
+    def asyncSetUp(self):
+        self.client = make_client()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 2);
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("asyncSetUp must be declared with async def")));
+}
+
+#[test]
+fn setup_declared_async() {
+    let src = r#"
+class AsyncTest(IsolatedAsyncioTestCase):
+    async def setUp(self):
+        self.client = await make_client()
+        super().setUp()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("setUp must not be declared with async def; use asyncSetUp"));
+}
+
+#[test]
+fn setup_class_declared_async() {
+    let src = r#"
+class AsyncTest(IsolatedAsyncioTestCase):
+    @classmethod
+    async def setUpClass(cls):
+        super().setUpClass()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("setUpClass must not be declared with async def"));
+}
+
+#[test]
+fn async_hooks_wrong_casing() {
+    let src = r#"
+class AsyncTest(IsolatedAsyncioTestCase):
+    async def asyncSetup(self):
+        await super().asyncSetUp()
+
+    async def asyncteardown(self):
+        await super().asyncTearDown()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 2);
+    assert!(errors
+        .iter()
+        .all(|e| e.contains("use correct casing: asyncSetUp / asyncTearDown")));
+}
+
+#[test]
+fn async_setup_pass_only() {
+    let src = r#"
+class AsyncTest(IsolatedAsyncioTestCase):
+    async def asyncSetUp(self):
+        pass
+"#;
+    assert!(run(src).is_empty());
+}
@@ -0,0 +1,216 @@
+// tests/attribute_clobbering.rs
+use pytest_super_hooks::check_file;
+use std::fs::{create_dir_all, write};
+use tempfile::TempDir;
+
+// Test suite for attributes a subclass's setUp assigns that the ancestors' setUp overwrites
+
+fn run(src: &str) -> Vec<String> {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    check_file(&file_path)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+const BASE: &str = r#"
+from unittest import TestCase
+
+class ApiBase(TestCase):
+    def setUp(self):
+        self.client = Client()
+        self.user = make_user()
+        super().setUp()
+"#;
+
+#[test]
+fn overwritten_attribute_flagged() {
+    let src = format!(
+        "{}{}",
+        BASE,
+        r#"
+class TestAdmin(ApiBase):
+    def setUp(self):
+        self.user = make_admin()
+        super().setUp()
+"#
+    );
+    let errors = run(&src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains(
+        ":12:9 PSH018 self.user is overwritten by super().setUp(): ApiBase.setUp assigns it again ("
+    ));
+    assert!(errors[0].contains("test.py:7)"));
+}
+
+#[test]
+fn each_attribute_reported_once() {
+    let src = format!(
+        "{}{}",
+        BASE,
+        r#"
+class TestAdmin(ApiBase):
+    def setUp(self):
+        self.user = make_admin()
+        if self.fast:
+            self.user = make_guest()
+        self.client = AdminClient()
+        super().setUp()
+"#
+    );
+    let errors = run(&src);
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].contains(":12:9 PSH018 self.user is overwritten"));
+    assert!(errors[1].contains(":15:9 PSH018 self.client is overwritten"));
+}
+
+#[test]
+fn attribute_assigned_again_after_super_accepted() {
+    let src = format!(
+        "{}{}",
+        BASE,
+        r#"
+class TestAdmin(ApiBase):
+    def setUp(self):
+        self.user = make_admin()
+        super().setUp()
+        self.user = make_admin()
+"#
+    );
+    let errors = run(&src);
+    assert!(errors.iter().all(|error| !error.contains("is overwritten")));
+}
+
+#[test]
+fn attribute_ancestors_do_not_assign_accepted() {
+    let src = format!(
+        "{}{}",
+        BASE,
+        r#"
+class TestAdmin(ApiBase):
+    def setUp(self):
+        self.admin = True
+        super().setUp()
+"#
+    );
+    assert!(run(&src).is_empty());
+}
+
+#[test]
+fn guarded_ancestor_assignment_accepted() {
+    let src = r#"
+from unittest import TestCase
+
+class ApiBase(TestCase):
+    def setUp(self):
+        if not hasattr(self, "user"):
+            self.user = make_user()
+        self.client = getattr(self, "client", None) or Client()
+        super().setUp()
+
+class TestAdmin(ApiBase):
+    def setUp(self):
+        self.user = make_admin()
+        self.client = AdminClient()
+        super().setUp()
+"#;
+    assert!(run(src).is_empty());
+}
+
+#[test]
+fn getattr_without_default_is_not_a_guard() {
+    // Without a default, getattr raises when the attribute is missing instead of probing it
+    let src = r#"
+from unittest import TestCase
+
+class ApiBase(TestCase):
+    def setUp(self):
+        self.client = getattr(self, "client") or Client()
+        super().setUp()
+
+class TestAdmin(ApiBase):
+    def setUp(self):
+        self.client = AdminClient()
+        super().setUp()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains(":11:9 PSH018 self.client is overwritten"));
+}
+
+#[test]
+fn grandparent_overwrite_flagged() {
+    let src = format!(
+        "{}{}",
+        BASE,
+        r#"
+class AdminBase(ApiBase):
+    def setUp(self):
+        self.admin = True
+        super().setUp()
+
+class TestAdmin(AdminBase):
+    def setUp(self):
+        self.client = AdminClient()
+        super().setUp()
+"#
+    );
+    let errors = run(&src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0]
+        .contains("self.client is overwritten by super().setUp(): ApiBase.setUp assigns it again"));
+}
+
+#[test]
+fn overwrite_from_other_file_flagged() {
+    let temp = TempDir::new().unwrap();
+    create_dir_all(temp.path().join("support")).unwrap();
+    write(temp.path().join("support/__init__.py"), "").unwrap();
+    write(
+        temp.path().join("support/base.py"),
+        "from unittest import IsolatedAsyncioTestCase\n\nclass ServiceBase(IsolatedAsyncioTestCase):\n    async def asyncSetUp(self):\n        self.service = await start()\n        await super().asyncSetUp()\n",
+    )
+    .unwrap();
+    let test_path = temp.path().join("test_service.py");
+    write(
+        &test_path,
+        "from support.base import ServiceBase\n\nclass TestService(ServiceBase):\n    async def asyncSetUp(self):\n        self.service = FakeService()\n        await super().asyncSetUp()\n",
+    )
+    .unwrap();
+
+    let errors = check_file(&test_path)
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains(
+        "test_service.py:5:9 PSH018 self.service is overwritten by await super().asyncSetUp(): ServiceBase.asyncSetUp assigns it again ("
+    ));
+    assert!(errors[0].contains("base.py:5)"));
+}
+
+#[test]
+fn class_fixture_overwrite_flagged() {
+    let src = r#"
+from django.test import TestCase
+
+class ServerBase(TestCase):
+    @classmethod
+    def setUpClass(cls):
+        cls.server = start_server()
+        super().setUpClass()
+
+class TestServer(ServerBase):
+    @classmethod
+    def setUpClass(cls):
+        cls.server = start_fake_server()
+        super().setUpClass()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains(
+        "cls.server is overwritten by super().setUpClass(): ServerBase.setUpClass assigns it again"
+    ));
+}
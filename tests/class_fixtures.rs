@@ -0,0 +1,184 @@
+// tests/class_fixtures.rs
+use pytest_super_hooks::check_file;
+use std::fs::write;
+use tempfile::TempDir;
+
+// Test suite for class-level fixtures (setUpClass / tearDownClass)
+
+fn run(src: &str) -> Vec<String> {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    check_file(&file_path)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn setup_class_valid() {
+    let src = r#"
+class DatabaseTest(TestCase):
+    @classmethod
+    def setUpClass(cls):
+        cls.db = connect()
+        super().setUpClass()
+
+    @classmethod
+    def tearDownClass(cls):
+        cls.db.close()
+        super().tearDownClass()
+"#;
+    assert!(run(src).is_empty());
+}
+
+#[test]
+fn setup_class_missing_super() {
+    let src = r#"
+class DatabaseTest(TestCase):
+    @classmethod
+    def setUpClass(cls):
+        cls.db = connect()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("super().setUpClass() must be the last line"));
+}
+
+#[test]
+fn teardown_class_super_not_last() {
+    let src = r#"
+class DatabaseTest(TestCase):
+    @classmethod
+    def tearDownClass(cls):
+        super().tearDownClass()
+        cls.db.close()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("super().tearDownClass() must be the last line"));
+}
+
+#[test]
+fn setup_class_wrong_super_target() {
+    let src = r#"
+class DatabaseTest(TestCase):
+    @classmethod
+    def setUpClass(cls):
+        cls.db = connect()
+        super().setUp()
+"#;
+    assert_eq!(run(src).len(), 1);
+}
+
+#[test]
+fn setup_class_without_classmethod() {
+    let src = r#"
+class DatabaseTest(TestCase):
+    def setUpClass(cls):
+        cls.db = connect()
+        super().setUpClass()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("setUpClass must be decorated with @classmethod"));
+}
+
+#[test]
+fn setup_class_pass_only() {
+    let src = r#"
+class DatabaseTest(TestCase):
+    @classmethod
+    def setUpClass(cls):
+        pass
+"#;
+    assert!(run(src).is_empty());
+}
+
+#[test]
+fn setup_class_wrong_casing() {
+    let src = r#"
+class DatabaseTest(TestCase):
+    @classmethod
+    def setupClass(cls):
+        super().setUpClass()
+
+    @classmethod
+    def teardownclass(cls):
+        super().tearDownClass()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 2);
+    assert!(errors
+        .iter()
+        .all(|e| e.contains("use correct casing: setUpClass / tearDownClass")));
+}
+
+#[test]
+fn setup_class_alongside_setup() {
+    let src = r#"
+class DatabaseTest(TestCase):
+    @classmethod
+    def setUpClass(cls):
+        super().setUpClass()
+        cls.db = connect()
+
+    def setUp(self):
+        self.cursor = self.db.cursor()
+        super().setUp()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("setUpClass"));
+}
+
+#[test]
+fn setup_test_data_valid() {
+    let src = r#"
+class ArticleTests(TestCase):
+    @classmethod
+    def setUpTestData(cls):
+        cls.author = Author.objects.create(name="Ada")
+        super().setUpTestData()
+"#;
+    assert!(run(src).is_empty());
+}
+
+#[test]
+fn setup_test_data_missing_super() {
+    let src = r#"
+class ArticleTests(TestCase):
+    @classmethod
+    def setUpTestData(cls):
+        cls.author = Author.objects.create(name="Ada")
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("super().setUpTestData() must be the last line"));
+}
+
+#[test]
+fn setup_test_data_without_classmethod() {
+    let src = r#"
+class ArticleTests(TestCase):
+    def setUpTestData(cls):
+        cls.author = Author.objects.create(name="Ada")
+        super().setUpTestData()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("setUpTestData must be decorated with @classmethod"));
+}
+
+#[test]
+fn setup_test_data_wrong_casing() {
+    let src = r#"
+class ArticleTests(TestCase):
+    @classmethod
+    def setupTestData(cls):
+        super().setUpTestData()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("use correct casing: setUpTestData"));
+}
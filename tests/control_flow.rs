@@ -0,0 +1,286 @@
+// tests/control_flow.rs
+use pytest_super_hooks::{check_file, fix::fix_file};
+use std::fs::{read_to_string, write};
+use tempfile::TempDir;
+
+// Test suite for control-flow-aware super() verification
+
+fn run(src: &str) -> Vec<String> {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    check_file(&file_path)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn super_at_end_of_every_branch() {
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        if self.fast:
+            self.db = memory_db()
+            super().setUp()
+        elif self.remote:
+            self.db = remote_db()
+            super().setUp()
+        else:
+            self.db = local_db()
+            super().setUp()
+"#;
+    assert!(run(src).is_empty());
+}
+
+#[test]
+fn super_missing_from_one_branch() {
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        if self.fast:
+            self.db = memory_db()
+            super().setUp()
+        else:
+            self.db = local_db()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains(
+        "super().setUp() must be the last line; it is skipped when `if self.fast` is false (line 4)"
+    ));
+}
+
+#[test]
+fn super_only_in_if_without_else() {
+    let src = r#"
+class T(TestCase):
+    def tearDown(self):
+        self.cleanup()
+        if self.started:
+            super().tearDown()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("it is skipped when `if self.started` is false (line 5)"));
+}
+
+#[test]
+fn early_return_in_elif_reports_path() {
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        if self.a:
+            self.x = 1
+        elif self.b:
+            return
+        super().setUp()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0]
+        .contains("it is skipped when `elif self.b` is true (line 6) and it returns at line 7"));
+}
+
+#[test]
+fn raise_and_skip_test_paths_are_exempt() {
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        if not HAVE_DB:
+            self.skipTest("no database")
+        if self.broken:
+            raise RuntimeError("broken")
+        self.db = connect()
+        super().setUp()
+"#;
+    assert!(run(src).is_empty());
+}
+
+#[test]
+fn super_in_loop_may_not_run() {
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        for attempt in range(3):
+            super().setUp()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("it is skipped when the `for` loop runs zero times (line 4)"));
+}
+
+#[test]
+fn super_in_try_and_handler() {
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        try:
+            self.db = connect()
+            super().setUp()
+        except ConnectionError:
+            self.db = None
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0]
+        .contains("it is skipped when `except ConnectionError` catches an exception (line 7)"));
+}
+
+#[test]
+fn super_after_with_block() {
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        with open(self.path) as f:
+            self.data = f.read()
+            super().setUp()
+"#;
+    assert!(run(src).is_empty());
+}
+
+#[test]
+fn match_without_wildcard_falls_through() {
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        match self.backend:
+            case "sqlite":
+                self.db = sqlite()
+                super().setUp()
+            case _:
+                self.db = other()
+                super().setUp()
+"#;
+    assert!(run(src).is_empty());
+
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        match self.backend:
+            case "sqlite":
+                self.db = sqlite()
+                super().setUp()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("no case of `match self.backend` matches (line 4)"));
+}
+
+#[test]
+fn fix_leaves_branch_calls_alone() {
+    let src = r#"class T(TestCase):
+    def setUp(self):
+        if self.fast:
+            self.db = memory_db()
+            super().setUp()
+        else:
+            self.db = local_db()
+"#;
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+
+    assert!(!fix_file(&file_path));
+    assert_eq!(read_to_string(&file_path).unwrap(), src);
+}
+
+#[test]
+fn fix_leaves_methods_that_return_alone() {
+    // Moving the call to the end would make the early return skip it
+    let src = r#"class T(TestCase):
+    def setUp(self):
+        super().setUp()
+        self.db = local_db()
+        if self.fast:
+            return
+        self.cache = Cache()
+"#;
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+
+    assert_eq!(run(src).len(), 1);
+    assert!(!fix_file(&file_path));
+    assert_eq!(read_to_string(&file_path).unwrap(), src);
+}
+
+#[test]
+fn statement_after_conditional_call_reports_false_branch() {
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        if self.fast:
+            super().setUp()
+        self.db = memory_db()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("it is skipped when `if self.fast` is false (line 4)"));
+}
+
+#[test]
+fn if_without_else_after_call_blames_the_branch_that_runs_code() {
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        super().setUp()
+        if self.fast:
+            self.db = memory_db()
+        self.cache = Cache()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].ends_with(
+        ":6:13 PSH002 super().setUp() must be the last line; more code runs after it when `if self.fast` is true (line 5)"
+    ));
+}
+
+#[test]
+fn for_else_after_call() {
+    // Zero iterations still run the `else`, and the loop itself runs after the call
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        super().setUp()
+        for item in self.items:
+            self.load(item)
+        else:
+            self.loaded = True
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].ends_with(":6:13 PSH002 super().setUp() must be the last line"));
+
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        for backend in self.backends:
+            if backend.ready:
+                break
+        else:
+            super().setUp()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains(
+        "it is skipped when `if backend.ready` is true (line 5) and it breaks out at line 6"
+    ));
+}
+
+#[test]
+fn handler_after_call_runs_code_after_it() {
+    // The exception comes from connect(), after the parent's setUp ran
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        try:
+            super().setUp()
+            self.db = connect()
+        except OSError:
+            self.db = None
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].ends_with(":6:13 PSH002 super().setUp() must be the last line"));
+}
@@ -0,0 +1,323 @@
+// tests/diagnostics.rs
+use pytest_super_hooks::{check_file, fix::fix_file, Diagnostic, Location, Rule, Severity};
+use std::fs::write;
+use tempfile::TempDir;
+
+// Test suite for the structured diagnostics the checks report
+
+fn run(src: &str) -> Vec<Diagnostic> {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    check_file(&file_path)
+}
+
+#[test]
+fn diagnostic_fields() {
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setUp(self):
+        super().setUp()
+        self.widget = Widget()
+"#;
+    let diagnostics = run(src);
+    assert_eq!(diagnostics.len(), 1);
+    let diagnostic = &diagnostics[0];
+    assert_eq!(diagnostic.rule, Rule::SuperPosition);
+    assert_eq!(diagnostic.severity, Severity::Error);
+    assert!(diagnostic.file.ends_with("test.py"));
+    assert_eq!(diagnostic.start, Location { line: 7, column: 9 });
+    assert_eq!(
+        diagnostic.end,
+        Location {
+            line: 7,
+            column: 31
+        }
+    );
+    assert_eq!(diagnostic.message, "super().setUp() must be the last line");
+    assert_eq!(
+        diagnostic.fix.as_ref().unwrap().message,
+        "Move super().setUp() to the end"
+    );
+    assert_eq!(diagnostic.class.as_deref(), Some("TestWidget"));
+    assert_eq!(diagnostic.method.as_deref(), Some("setUp"));
+}
+
+#[test]
+fn text_output_renders_path_line_and_message() {
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setup(self):
+        self.widget = Widget()
+"#;
+    let diagnostics = run(src);
+    assert_eq!(diagnostics.len(), 1);
+    let text = diagnostics[0].to_string();
+    assert!(text.ends_with("test.py:5:9 PSH001 use correct casing: setUp / tearDown"));
+    assert_eq!(diagnostics[0].rule, Rule::MethodCasing);
+    assert_eq!(
+        diagnostics[0].fix.as_ref().unwrap().message,
+        "Rename to setUp"
+    );
+}
+
+#[test]
+fn module_level_function_has_no_class() {
+    let src = r#"
+import unittest
+
+def setUpModule(self):
+    connect()
+"#;
+    let diagnostics = run(src);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].rule, Rule::ModuleFixtureSignature);
+    assert_eq!(diagnostics[0].start.line, 4);
+    assert_eq!(diagnostics[0].class, None);
+    assert_eq!(diagnostics[0].method.as_deref(), Some("setUpModule"));
+    assert!(diagnostics[0].fix.is_none());
+    assert_eq!(diagnostics[1].rule, Rule::MissingModuleTeardown);
+    assert_eq!(diagnostics[1].severity, Severity::Warning);
+    assert_eq!(diagnostics[1].method.as_deref(), Some("setUpModule"));
+}
+
+#[test]
+fn pass_only_method_has_no_fix() {
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setup(self):
+        pass
+"#;
+    let diagnostics = run(src);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].fix.is_none());
+}
+
+#[test]
+fn early_return_has_no_fix() {
+    // The call is already at the end; moving it there again can't reach the return path
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setUp(self):
+        if self.fast:
+            return
+        super().setUp()
+"#;
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    let diagnostics = check_file(&file_path);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].rule, Rule::SuperPosition);
+    assert!(diagnostics[0].fix.is_none());
+
+    assert!(!fix_file(&file_path));
+    assert_eq!(check_file(&file_path), diagnostics);
+}
+
+#[test]
+fn nested_call_has_no_fix() {
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setUp(self):
+        if self.fast:
+            super().setUp()
+        self.widget = Widget()
+"#;
+    let diagnostics = run(src);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].fix.is_none());
+}
+
+#[test]
+fn class_level_rules_name_the_method() {
+    let src = r#"
+from unittest import TestCase
+
+class ApiBase(TestCase):
+    def setUp(self):
+        self.client = Client()
+
+class TestApi(ApiBase):
+    pass
+"#;
+    let diagnostics = run(src);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].rule, Rule::SuperPosition);
+    assert_eq!(diagnostics[0].class.as_deref(), Some("ApiBase"));
+    assert_eq!(diagnostics[1].rule, Rule::BrokenInheritedChain);
+    assert_eq!(diagnostics[1].start, Location { line: 8, column: 1 });
+    assert_eq!(diagnostics[1].class.as_deref(), Some("TestApi"));
+    assert_eq!(diagnostics[1].method.as_deref(), Some("setUp"));
+}
+
+#[test]
+fn casing_points_at_method_name() {
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    @property
+    def tearDown(self):
+        pass
+
+    def Setup(self):
+        super().setUp()
+"#;
+    let diagnostics = run(src);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].rule, Rule::MethodCasing);
+    assert_eq!(diagnostics[0].start, Location { line: 9, column: 9 });
+    assert_eq!(
+        diagnostics[0].end,
+        Location {
+            line: 9,
+            column: 14
+        }
+    );
+}
+
+#[test]
+fn position_errors_point_at_offending_statement() {
+    // The early return that skips the call
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setUp(self):
+        if self.fast:
+            return
+        super().setUp()
+"#;
+    let diagnostics = run(src);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].start,
+        Location {
+            line: 7,
+            column: 13
+        }
+    );
+    assert_eq!(
+        diagnostics[0].end,
+        Location {
+            line: 7,
+            column: 19
+        }
+    );
+
+    // A missing call points at the method's name
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setUp(self):
+        self.widget = Widget()
+"#;
+    let diagnostics = run(src);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].start, Location { line: 5, column: 9 });
+    assert_eq!(
+        diagnostics[0].end,
+        Location {
+            line: 5,
+            column: 14
+        }
+    );
+}
+
+#[test]
+fn unawaited_super_points_at_call() {
+    let src = r#"
+from unittest import IsolatedAsyncioTestCase
+
+class TestWidget(IsolatedAsyncioTestCase):
+    async def asyncSetUp(self):
+        self.widget = Widget()
+        super().asyncSetUp()
+"#;
+    let diagnostics = run(src);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].rule, Rule::UnawaitedSuper);
+    assert_eq!(diagnostics[0].start, Location { line: 7, column: 9 });
+    assert_eq!(
+        diagnostics[0].end,
+        Location {
+            line: 7,
+            column: 29
+        }
+    );
+}
+
+#[test]
+fn columns_count_characters() {
+    let src = "from unittest import TestCase\n\nclass TestWidget(TestCase):\n    def setUp(self):\n        self.émoji = '🙂'; self.widget = Widget()\n";
+    let diagnostics = run(src);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].start, Location { line: 4, column: 9 });
+
+    let src = "from unittest import TestCase\n\nclass TestWidget(TestCase):\n    def setUp(self):\n        super().setUp(); self.label = 'é'; self.widget = Widget()\n";
+    let diagnostics = run(src);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].start,
+        Location {
+            line: 5,
+            column: 26
+        }
+    );
+    assert_eq!(
+        diagnostics[0].end,
+        Location {
+            line: 5,
+            column: 42
+        }
+    );
+}
+
+#[test]
+fn serializes_to_json() {
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setupClass(cls):
+        super().setUpClass()
+"#;
+    let diagnostics = run(src);
+    assert_eq!(diagnostics.len(), 1);
+    let mut json = serde_json::to_value(&diagnostics[0]).unwrap();
+    json["file"] = "test.py".into();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "rule": "PSH001",
+            "severity": "error",
+            "file": "test.py",
+            "start": {"line": 5, "column": 9},
+            "end": {"line": 5, "column": 19},
+            "message": "use correct casing: setUpClass / tearDownClass",
+            "labels": [],
+            "fix": {
+                "message": "Rename to setUpClass",
+                "edits": [{
+                    "start": {"line": 5, "column": 1},
+                    "end": {"line": 6, "column": 29},
+                    "content": "    @classmethod\n    def setUpClass(cls):\n        super().setUpClass()"
+                }]
+            },
+            "class": "TestWidget",
+            "method": "setupClass"
+        })
+    );
+}
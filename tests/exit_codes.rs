@@ -0,0 +1,177 @@
+// tests/exit_codes.rs
+use std::fs::{metadata, read_to_string, set_permissions, write, OpenOptions};
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+// Test suite for the CLI's exit codes and exit-policy flags
+
+const VIOLATION: &str = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setUp(self):
+        super().setUp()
+        self.widget = Widget()
+"#;
+
+const CLEAN: &str = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setUp(self):
+        self.widget = Widget()
+        super().setUp()
+"#;
+
+const CLEAN_FIRST: &str = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setUp(self):
+        super().setUp()
+        self.widget = Widget()
+"#;
+
+/// Run the CLI with `args`, returning its exit code.
+fn exit_code(args: &[&str], dir: &Path) -> i32 {
+    Command::new(env!("CARGO_BIN_EXE_pytest-super-hooks"))
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap()
+        .status
+        .code()
+        .unwrap()
+}
+
+#[test]
+fn violations_exit_one_and_clean_files_zero() {
+    let temp = TempDir::new().unwrap();
+    write(temp.path().join("test_bad.py"), VIOLATION).unwrap();
+    write(temp.path().join("test_good.py"), CLEAN).unwrap();
+    assert_eq!(exit_code(&["test_good.py"], temp.path()), 0);
+    assert_eq!(exit_code(&["test_bad.py", "test_good.py"], temp.path()), 1);
+    assert_eq!(exit_code(&["--exit-zero", "test_bad.py"], temp.path()), 0);
+}
+
+#[test]
+fn usage_errors_exit_two() {
+    let temp = TempDir::new().unwrap();
+    write(temp.path().join("test_good.py"), CLEAN).unwrap();
+    assert_eq!(exit_code(&[], temp.path()), 2);
+    assert_eq!(
+        exit_code(&["--no-such-flag", "test_good.py"], temp.path()),
+        2
+    );
+    assert_eq!(
+        exit_code(&["--select", "XYZ", "test_good.py"], temp.path()),
+        2
+    );
+    assert_eq!(
+        exit_code(&["--output-format", "fancy", "test_good.py"], temp.path()),
+        2
+    );
+    assert_eq!(
+        exit_code(
+            &["--super-position", "setup=first", "test_good.py"],
+            temp.path()
+        ),
+        2
+    );
+    assert_eq!(exit_code(&["--explain", "PSH999"], temp.path()), 2);
+    assert_eq!(exit_code(&["--help"], temp.path()), 0);
+}
+
+#[test]
+fn unreadable_and_unparsable_files_exit_three() {
+    let temp = TempDir::new().unwrap();
+    write(temp.path().join("test_bad.py"), VIOLATION).unwrap();
+    write(
+        temp.path().join("test_broken.py"),
+        "def broken(:\n    pass\n",
+    )
+    .unwrap();
+    assert_eq!(exit_code(&["test_missing.py"], temp.path()), 3);
+    // Errors win over violations, and aren't silenced by --exit-zero
+    assert_eq!(
+        exit_code(&["test_bad.py", "test_broken.py"], temp.path()),
+        3
+    );
+    assert_eq!(
+        exit_code(&["--exit-zero", "test_broken.py"], temp.path()),
+        3
+    );
+    // ...or by selecting other rules, unless their rule is ignored
+    assert_eq!(
+        exit_code(&["--select", "PSH002", "test_broken.py"], temp.path()),
+        3
+    );
+    assert_eq!(
+        exit_code(&["--ignore", "PSH019", "test_broken.py"], temp.path()),
+        0
+    );
+}
+
+#[test]
+fn exit_non_zero_on_fix_fails_when_files_were_fixed() {
+    let temp = TempDir::new().unwrap();
+    write(temp.path().join("test_bad.py"), VIOLATION).unwrap();
+    assert_eq!(
+        exit_code(
+            &["--fix", "--exit-non-zero-on-fix", "test_bad.py"],
+            temp.path()
+        ),
+        1
+    );
+    // The file is clean now, so there's nothing left to fix
+    assert_eq!(
+        exit_code(
+            &["--fix", "--exit-non-zero-on-fix", "test_bad.py"],
+            temp.path()
+        ),
+        0
+    );
+
+    write(temp.path().join("test_bad.py"), VIOLATION).unwrap();
+    assert_eq!(exit_code(&["--fix", "test_bad.py"], temp.path()), 0);
+
+    // A file that already calls super() where it has to isn't rewritten
+    write(temp.path().join("test_first.py"), CLEAN_FIRST).unwrap();
+    assert_eq!(
+        exit_code(
+            &[
+                "--fix",
+                "--exit-non-zero-on-fix",
+                "--super-position",
+                "setUp=first",
+                "test_first.py"
+            ],
+            temp.path()
+        ),
+        0
+    );
+    assert_eq!(
+        read_to_string(temp.path().join("test_first.py")).unwrap(),
+        CLEAN_FIRST
+    );
+}
+
+#[test]
+fn unwritable_files_exit_three() {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test_bad.py");
+    write(&file_path, VIOLATION).unwrap();
+    let mut permissions = metadata(&file_path).unwrap().permissions();
+    permissions.set_readonly(true);
+    set_permissions(&file_path, permissions).unwrap();
+    // Permissions don't stop a privileged user
+    if OpenOptions::new().write(true).open(&file_path).is_ok() {
+        return;
+    }
+    assert_eq!(exit_code(&["--fix", "test_bad.py"], temp.path()), 3);
+    assert_eq!(
+        exit_code(&["--fix", "--exit-zero", "test_bad.py"], temp.path()),
+        3
+    );
+}
@@ -2,7 +2,7 @@ use pytest_super_hooks::{check_file, fix::fix_file};
 use std::fs::write;
 use tempfile::TempDir;
 
-/// Test suite for the --fix functionality
+// Test suite for the --fix functionality
 
 fn run_fix(src: &str) -> (Vec<String>, String) {
     let temp = TempDir::new().unwrap();
@@ -13,7 +13,10 @@ fn run_fix(src: &str) -> (Vec<String>, String) {
     fix_file(&file_path);
 
     // Check for remaining errors
-    let errors = check_file(&file_path);
+    let errors = check_file(&file_path)
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
 
     // Read the fixed content
     let fixed = std::fs::read_to_string(&file_path).unwrap();
@@ -23,7 +26,7 @@ fn run_fix(src: &str) -> (Vec<String>, String) {
 
 #[test]
 fn fix_missing_super_in_setup() {
-    let src = r#"class T:
+    let src = r#"class T(TestCase):
     def setUp(self):
         self.value = 1"#;
 
@@ -36,7 +39,7 @@ fn fix_missing_super_in_setup() {
 
 #[test]
 fn fix_missing_super_in_teardown() {
-    let src = r#"class T:
+    let src = r#"class T(TestCase):
     def tearDown(self):
         self.cleanup()"#;
 
@@ -49,7 +52,7 @@ fn fix_missing_super_in_teardown() {
 
 #[test]
 fn fix_wrong_casing_setup() {
-    let src = r#"class T:
+    let src = r#"class T(TestCase):
     def setup(self):
         self.x = 1
         super().setUp()"#;
@@ -62,7 +65,7 @@ fn fix_wrong_casing_setup() {
 
 #[test]
 fn fix_wrong_casing_teardown() {
-    let src = r#"class T:
+    let src = r#"class T(TestCase):
     def teardown(self):
         self.cleanup()
         super().tearDown()"#;
@@ -75,7 +78,7 @@ fn fix_wrong_casing_teardown() {
 
 #[test]
 fn fix_super_not_last() {
-    let src = r#"class T:
+    let src = r#"class T(TestCase):
     def setUp(self):
         super().setUp()
         self.value = 1"#;
@@ -100,7 +103,7 @@ fn fix_super_not_last() {
 
 #[test]
 fn fix_multiple_methods() {
-    let src = r#"class T:
+    let src = r#"class T(TestCase):
     def setUp(self):
         self.a = 1
 
@@ -116,7 +119,7 @@ fn fix_multiple_methods() {
 
 #[test]
 fn fix_preserves_other_code() {
-    let src = r#"class TestExample:
+    let src = r#"class TestExample(TestCase):
     def setUp(self):
         self.db = Database()
         self.logger = Logger()
@@ -128,7 +131,7 @@ fn fix_preserves_other_code() {
     let (errors, fixed) = run_fix(src);
 
     assert!(errors.is_empty());
-    assert!(fixed.contains("class TestExample:"));
+    assert!(fixed.contains("class TestExample(TestCase):"));
     assert!(fixed.contains("def test_something(self):"));
     assert!(fixed.contains("\"\"\"Test method.\"\"\""));
     assert!(fixed.contains("assert True"));
@@ -139,7 +142,7 @@ fn fix_preserves_other_code() {
 
 #[test]
 fn fix_indentation_preserved() {
-    let src = r#"class TestClass:
+    let src = r#"class TestClass(TestCase):
     def setUp(self):
         x = 1
         y = 2
@@ -156,7 +159,7 @@ fn fix_indentation_preserved() {
 
 #[test]
 fn fix_handles_complex_setup() {
-    let src = r#"class ComplexTest:
+    let src = r#"class ComplexTest(TestCase):
     def setUp(self):
         self.config = {
             "debug": True,
@@ -180,7 +183,7 @@ fn fix_multiple_classes() {
     // Note: When fixing multiple methods, later ones may not be fixed
     // correctly due to line offset changes in the current implementation.
     // This test validates that at least one class gets fixed.
-    let src = r#"class TestOne:
+    let src = r#"class TestOne(TestCase):
     def setUp(self):
         self.one = 1"#;
 
@@ -196,7 +199,7 @@ fn fix_mixed_issues() {
     // are based on the original source. When multiple methods need fixing,
     // the later ones may not be fixed correctly due to line offset changes.
     // For now, we test that at least the first method is fixed correctly.
-    let src = r#"class MixedTest:
+    let src = r#"class MixedTest(TestCase):
     def setup(self):
         self.prepare()"#;
 
@@ -211,7 +214,7 @@ fn fix_mixed_issues() {
 
 #[test]
 fn fix_docstring_preserved() {
-    let src = r#"class DocTest:
+    let src = r#"class DocTest(TestCase):
     def setUp(self):
         """Initialize fixtures."""
         self.fixture = create_fixture()"#;
@@ -225,7 +228,7 @@ fn fix_docstring_preserved() {
 
 #[test]
 fn fix_with_comments() {
-    let src = r#"class CommentTest:
+    let src = r#"class CommentTest(TestCase):
     def setUp(self):
         # Initialize
         self.x = 1  # important
@@ -243,7 +246,7 @@ fn fix_with_comments() {
 
 #[test]
 fn fix_multiline_statements() {
-    let src = r#"class MultilineTest:
+    let src = r#"class MultilineTest(TestCase):
     def setUp(self):
         self.data = {
             "key1": "value1",
@@ -268,7 +271,7 @@ fn fix_multiline_statements() {
 
 #[test]
 fn fix_empty_method_unchanged() {
-    let src = r#"class EmptyTest:
+    let src = r#"class EmptyTest(TestCase):
     def setUp(self):
         pass"#;
 
@@ -312,7 +315,7 @@ fn fix_real_world_django_test() {
 
 #[test]
 fn fix_only_fixes_setup_teardown() {
-    let src = r#"class OnlyOthersTest:
+    let src = r#"class OnlyOthersTest(TestCase):
     def helper_setup(self):
         self.value = 1
 
@@ -332,3 +335,232 @@ fn fix_only_fixes_setup_teardown() {
     // No super() should be added
     assert!(!fixed.contains("super().setUp()"));
 }
+
+#[test]
+fn fix_missing_super_in_setup_class() {
+    let src = r#"class T(TestCase):
+    @classmethod
+    def setUpClass(cls):
+        cls.db = connect()"#;
+
+    let (errors, fixed) = run_fix(src);
+
+    assert!(errors.is_empty());
+    assert_eq!(
+        fixed,
+        r#"class T(TestCase):
+    @classmethod
+    def setUpClass(cls):
+        cls.db = connect()
+        super().setUpClass()"#
+    );
+}
+
+#[test]
+fn fix_wrong_casing_teardown_class() {
+    let src = r#"class T(TestCase):
+    @classmethod
+    def teardownclass(cls):
+        cls.db.close()"#;
+
+    let (errors, fixed) = run_fix(src);
+
+    assert!(errors.is_empty());
+    assert!(fixed.contains("def tearDownClass(cls):"));
+    assert!(fixed.contains("super().tearDownClass()"));
+}
+
+#[test]
+fn fix_adds_classmethod_decorator() {
+    let src = r#"class T(TestCase):
+    def setUpClass(cls):
+        cls.db = connect()
+        super().setUpClass()"#;
+
+    let (errors, fixed) = run_fix(src);
+
+    assert!(errors.is_empty());
+    assert_eq!(
+        fixed,
+        r#"class T(TestCase):
+    @classmethod
+    def setUpClass(cls):
+        cls.db = connect()
+        super().setUpClass()"#
+    );
+}
+
+#[test]
+fn fix_setup_class_does_not_touch_setup_super() {
+    let src = r#"class T(TestCase):
+    @classmethod
+    def setUpClass(cls):
+        cls.db = connect()
+
+    def setUp(self):
+        super().setUp()"#;
+
+    let (errors, fixed) = run_fix(src);
+
+    assert!(errors.is_empty());
+    assert!(fixed.contains("super().setUpClass()"));
+    assert!(fixed.contains("super().setUp()\n") || fixed.ends_with("super().setUp()"));
+}
+
+#[test]
+fn fix_multiple_methods_keeps_structure() {
+    let src = r#"class T(TestCase):
+    def setUp(self):
+        self.a = 1
+
+    def tearDown(self):
+        self.cleanup()"#;
+
+    let (errors, fixed) = run_fix(src);
+
+    assert!(errors.is_empty());
+    assert_eq!(
+        fixed,
+        r#"class T(TestCase):
+    def setUp(self):
+        self.a = 1
+        super().setUp()
+
+    def tearDown(self):
+        self.cleanup()
+        super().tearDown()"#
+    );
+}
+
+#[test]
+fn fix_missing_await_in_async_setup() {
+    let src = r#"class T(TestCase):
+    async def asyncSetUp(self):
+        self.client = await make_client()
+        super().asyncSetUp()"#;
+
+    let (errors, fixed) = run_fix(src);
+
+    assert!(errors.is_empty());
+    assert_eq!(
+        fixed,
+        r#"class T(TestCase):
+    async def asyncSetUp(self):
+        self.client = await make_client()
+        await super().asyncSetUp()"#
+    );
+}
+
+#[test]
+fn fix_missing_super_in_async_teardown() {
+    let src = r#"class T(TestCase):
+    async def asyncTearDown(self):
+        await self.client.close()"#;
+
+    let (errors, fixed) = run_fix(src);
+
+    assert!(errors.is_empty());
+    assert!(fixed.ends_with("        await super().asyncTearDown()"));
+}
+
+#[test]
+fn fix_sync_async_setup_and_casing() {
+    let src = r#"class T(TestCase):
+    def asyncSetup(self):
+        self.client = make_client()"#;
+
+    let (errors, fixed) = run_fix(src);
+
+    assert!(errors.is_empty());
+    assert!(fixed.contains("    async def asyncSetUp(self):"));
+    assert!(fixed.contains("await super().asyncSetUp()"));
+}
+
+#[test]
+fn fix_missing_super_in_setup_test_data() {
+    let src = r#"class ArticleTests(TestCase):
+    @classmethod
+    def setUpTestData(cls):
+        cls.author = Author.objects.create(name="Ada")"#;
+
+    let (errors, fixed) = run_fix(src);
+
+    assert!(errors.is_empty());
+    assert!(fixed.ends_with("        super().setUpTestData()"));
+}
+
+#[test]
+fn fix_setup_test_data_casing_and_decorator() {
+    let src = r#"class ArticleTests(TestCase):
+    def setuptestdata(cls):
+        cls.author = Author.objects.create(name="Ada")"#;
+
+    let (errors, fixed) = run_fix(src);
+
+    assert!(errors.is_empty());
+    assert_eq!(
+        fixed,
+        r#"class ArticleTests(TestCase):
+    @classmethod
+    def setUpTestData(cls):
+        cls.author = Author.objects.create(name="Ada")
+        super().setUpTestData()"#
+    );
+}
+
+#[test]
+fn fix_xunit_near_miss_names() {
+    let src = r#"def setupModule(module):
+    connect()
+
+class TestWidgets:
+    def setupMethod(self, method):
+        self.widget = Widget()
+
+    def Teardown_Method(self, method):
+        self.widget.destroy()"#;
+
+    let (errors, fixed) = run_fix(src);
+
+    assert!(errors.is_empty());
+    assert_eq!(
+        fixed,
+        r#"def setup_module(module):
+    connect()
+
+class TestWidgets:
+    def setup_method(self, method):
+        self.widget = Widget()
+
+    def teardown_method(self, method):
+        self.widget.destroy()"#
+    );
+}
+
+#[test]
+fn fix_module_fixture_names_in_unittest_module() {
+    let src = r#"import unittest
+
+def setup_module():
+    start_server()
+
+def teardownModule():
+    stop_server()"#;
+
+    let (errors, fixed) = run_fix(src);
+
+    assert!(errors.is_empty());
+    assert!(fixed.contains("def setUpModule():"));
+    assert!(fixed.contains("def tearDownModule():"));
+}
+
+#[test]
+fn fix_module_fixture_names_in_pytest_module() {
+    let src = r#"def setupModule(module):
+    start_server()"#;
+
+    let (errors, fixed) = run_fix(src);
+
+    assert!(errors.is_empty());
+    assert!(fixed.contains("def setup_module(module):"));
+}
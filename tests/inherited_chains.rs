@@ -0,0 +1,230 @@
+// tests/inherited_chains.rs
+use pytest_super_hooks::{check_file, check_files_with_config, Config};
+use std::fs::{create_dir_all, write};
+use tempfile::TempDir;
+
+// Test suite for reporting broken ancestor chains on leaf test classes
+
+/// Write `files` (relative path, source) into a fresh project directory.
+fn project(files: &[(&str, &str)]) -> TempDir {
+    let temp = TempDir::new().unwrap();
+    for (path, src) in files {
+        let path = temp.path().join(path);
+        create_dir_all(path.parent().unwrap()).unwrap();
+        write(&path, src).unwrap();
+    }
+    temp
+}
+
+const BROKEN_BASE: &str = r#"from unittest import TestCase
+
+class ApiBase(TestCase):
+    def setUp(self):
+        self.client = Client()
+"#;
+
+#[test]
+fn broken_base_in_other_file_reported_on_leaf() {
+    let temp = project(&[
+        ("base.py", BROKEN_BASE),
+        (
+            "test_api.py",
+            "from base import ApiBase\n\nclass TestApi(ApiBase):\n    def setUp(self):\n        self.user = make_user()\n        super().setUp()\n",
+        ),
+    ]);
+    let errors = check_file(&temp.path().join("test_api.py"))
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(errors[0].contains("inherited setUp chain is broken: TestApi -> ApiBase.setUp ("));
+    assert!(errors[0].contains("base.py:4) never calls super().setUp()"));
+}
+
+#[test]
+fn leaf_without_own_setup_reported() {
+    let temp = project(&[
+        ("base.py", BROKEN_BASE),
+        (
+            "test_api.py",
+            "from base import ApiBase\n\nclass TestApi(ApiBase):\n    def test_get(self):\n        pass\n",
+        ),
+    ]);
+    let errors = check_file(&temp.path().join("test_api.py"))
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("TestApi -> ApiBase.setUp"));
+}
+
+#[test]
+fn full_chain_across_files() {
+    let temp = project(&[
+        ("base.py", BROKEN_BASE),
+        (
+            "mixins.py",
+            "from base import ApiBase\n\nclass DbBase(ApiBase):\n    def setUp(self):\n        self.db = connect()\n        super().setUp()\n",
+        ),
+        (
+            "test_db.py",
+            "from mixins import DbBase\n\nclass TestDb(DbBase):\n    pass\n",
+        ),
+    ]);
+    let errors = check_file(&temp.path().join("test_db.py"))
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("TestDb -> DbBase -> ApiBase.setUp ("));
+}
+
+#[test]
+fn intermediate_class_not_reported() {
+    let src = r#"
+from base import ApiBase
+
+class Middle(ApiBase):
+    pass
+
+class TestLeaf(Middle):
+    pass
+"#;
+    let temp = project(&[("base.py", BROKEN_BASE), ("test_leaf.py", src)]);
+    let errors = check_file(&temp.path().join("test_leaf.py"))
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("TestLeaf -> Middle -> ApiBase.setUp"));
+}
+
+#[test]
+fn leaf_breaking_its_own_chain_not_reported_twice() {
+    let temp = project(&[
+        ("base.py", BROKEN_BASE),
+        (
+            "test_api.py",
+            "from base import ApiBase\n\nclass TestApi(ApiBase):\n    def setUp(self):\n        self.user = make_user()\n",
+        ),
+    ]);
+    let errors = check_file(&temp.path().join("test_api.py"))
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("super().setUp() must be the last line"));
+}
+
+#[test]
+fn cooperative_chain_accepted() {
+    let temp = project(&[
+        (
+            "base.py",
+            "from unittest import TestCase\n\nclass ApiBase(TestCase):\n    def setUp(self):\n        self.client = Client()\n        super(ApiBase, self).setUp()\n",
+        ),
+        (
+            "test_api.py",
+            "from base import ApiBase\n\nclass TestApi(ApiBase):\n    pass\n",
+        ),
+    ]);
+    assert!(check_file(&temp.path().join("test_api.py")).is_empty());
+}
+
+#[test]
+fn class_fixtures_and_async_hooks_reported() {
+    let base = r#"from unittest import IsolatedAsyncioTestCase
+
+class ServiceBase(IsolatedAsyncioTestCase):
+    @classmethod
+    def setUpClass(cls):
+        cls.service = start()
+
+    async def asyncTearDown(self):
+        await self.client.close()
+"#;
+    let temp = project(&[
+        ("base.py", base),
+        (
+            "test_service.py",
+            "from base import ServiceBase\n\nclass TestService(ServiceBase):\n    pass\n",
+        ),
+    ]);
+    let errors = check_file(&temp.path().join("test_service.py"))
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].contains("TestService -> ServiceBase.setUpClass ("));
+    assert!(errors[0].contains("never calls super().setUpClass()"));
+    assert!(errors[1].contains("never calls await super().asyncTearDown()"));
+}
+
+#[test]
+fn every_leaf_reports_the_break() {
+    let leaf = |name: &str| {
+        format!(
+            "from base import ApiBase\n\nclass {}(ApiBase):\n    pass\n",
+            name
+        )
+    };
+    let temp = project(&[
+        ("base.py", BROKEN_BASE),
+        ("test_a.py", &leaf("TestA")),
+        ("test_b.py", &leaf("TestB")),
+    ]);
+    let a = temp.path().join("test_a.py");
+    let b = temp.path().join("test_b.py");
+    let errors = check_files_with_config(&[a.as_path(), b.as_path()], &Config::default())
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].contains("TestA -> ApiBase.setUp"));
+    assert!(errors[1].contains("TestB -> ApiBase.setUp"));
+}
+
+#[test]
+fn root_ends_only_the_chains_it_defines() {
+    // unittest.TestCase has no setUpTestData or asyncSetUp, so nothing is skipped
+    let base = r#"from unittest import TestCase
+
+class ApiBase(TestCase):
+    @classmethod
+    def setUpTestData(cls):
+        cls.user = make_user()
+
+    async def asyncSetUp(self):
+        self.client = Client()
+"#;
+    let temp = project(&[
+        ("base.py", base),
+        (
+            "test_api.py",
+            "from base import ApiBase\n\nclass TestApi(ApiBase):\n    pass\n",
+        ),
+    ]);
+    assert!(check_file(&temp.path().join("test_api.py")).is_empty());
+
+    // Django's TestCase does have setUpTestData
+    let base = r#"from django.test import TestCase
+
+class ApiBase(TestCase):
+    @classmethod
+    def setUpTestData(cls):
+        cls.user = make_user()
+"#;
+    let temp = project(&[
+        ("base.py", base),
+        (
+            "test_api.py",
+            "from base import ApiBase\n\nclass TestApi(ApiBase):\n    pass\n",
+        ),
+    ]);
+    let errors = check_file(&temp.path().join("test_api.py"))
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("so django.test.TestCase.setUpTestData never runs"));
+}
@@ -10,7 +10,7 @@ fn valid_teardown() {
     write(
         &file_path,
         r#"
-class T:
+class T(TestCase):
     def tearDown(self):
         x = 1
         super().tearDown()
@@ -27,7 +27,7 @@ fn invalid_teardown() {
     write(
         &file_path,
         r#"
-class T:
+class T(TestCase):
     def tearDown(self):
         super().tearDown()
         x = 1
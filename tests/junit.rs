@@ -0,0 +1,183 @@
+// tests/junit.rs
+use pytest_super_hooks::{
+    output::{junit, Run},
+    report_files_with_config, Config, Report,
+};
+use std::{fs::write, path::Path};
+use tempfile::TempDir;
+
+// Test suite for the lifecycle methods a check reports and the JUnit XML built from them
+
+fn report(files: &[(&str, &str)]) -> (TempDir, Report) {
+    let temp = TempDir::new().unwrap();
+    for (name, src) in files {
+        write(temp.path().join(name), src).unwrap();
+    }
+    let paths: Vec<_> = files
+        .iter()
+        .map(|(name, _)| temp.path().join(name))
+        .collect();
+    let paths: Vec<&Path> = paths.iter().map(|path| path.as_path()).collect();
+    let mut report = report_files_with_config(&paths, &Config::default());
+    // Report relative paths, like the CLI does for relative arguments
+    for diagnostic in &mut report.diagnostics {
+        diagnostic.file = diagnostic.file.strip_prefix(temp.path()).unwrap().into();
+    }
+    for method in &mut report.methods {
+        method.file = method.file.strip_prefix(temp.path()).unwrap().into();
+    }
+    (temp, report)
+}
+
+fn xml(report: &Report, files: &[&str]) -> String {
+    let files: Vec<&Path> = files.iter().map(Path::new).collect();
+    junit(
+        &Run {
+            files_checked: &files,
+            files_fixed: &[],
+            diagnostics: &report.diagnostics,
+        },
+        &report.methods,
+    )
+}
+
+#[test]
+fn report_lists_checked_lifecycle_methods() {
+    let src = r#"
+from unittest import TestCase
+
+def setUpModule():
+    connect()
+
+def tearDownModule():
+    disconnect()
+
+class TestWidget(TestCase):
+    @classmethod
+    def setUpClass(cls):
+        super().setUpClass()
+
+    def setup(self):
+        self.widget = Widget()
+
+    def helper(self):
+        pass
+
+class Helper:
+    def setUp(self):
+        pass
+"#;
+    let (_temp, report) = report(&[("test_widget.py", src)]);
+    let methods: Vec<(Option<&str>, &str, usize)> = report
+        .methods
+        .iter()
+        .map(|method| {
+            (
+                method.class.as_deref(),
+                method.method.as_str(),
+                method.start.line,
+            )
+        })
+        .collect();
+    // Helper methods and classes that aren't test classes aren't lifecycle methods
+    assert_eq!(
+        methods,
+        [
+            (None, "setUpModule", 4),
+            (None, "tearDownModule", 7),
+            (Some("TestWidget"), "setUpClass", 12),
+            (Some("TestWidget"), "setup", 15),
+        ]
+    );
+    assert_eq!(report.diagnostics.len(), 1);
+    assert!(report.methods[3].is_reported_by(&report.diagnostics[0]));
+}
+
+#[test]
+fn junit_reports_passing_and_failing_methods() {
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setUp(self):
+        super().setUp()
+        self.widget = Widget()
+
+    def tearDown(self):
+        self.widget.close()
+        super().tearDown()
+"#;
+    let (_temp, report) = report(&[("test_widget.py", src), ("test_empty.py", "")]);
+    assert_eq!(
+        xml(&report, &["test_widget.py", "test_empty.py"]),
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="pytest-super-hooks" tests="2" failures="1" errors="0">
+  <testsuite name="test_widget.py" tests="2" failures="1" errors="0" skipped="0">
+    <testcase name="setUp" classname="test_widget.TestWidget" file="test_widget.py" line="5">
+      <failure type="PSH002" message="super().setUp() must be the last line">test_widget.py:7:9 PSH002 super().setUp() must be the last line</failure>
+    </testcase>
+    <testcase name="tearDown" classname="test_widget.TestWidget" file="test_widget.py" line="9"/>
+  </testsuite>
+  <testsuite name="test_empty.py" tests="0" failures="0" errors="0" skipped="0">
+  </testsuite>
+</testsuites>
+"#
+    );
+}
+
+#[test]
+fn junit_gives_inherited_violations_a_test_case() {
+    let src = r#"
+from unittest import TestCase
+
+class Mixin:
+    def setUp(self):
+        self.db = connect()
+
+class TestDb(Mixin, TestCase):
+    def test_query(self):
+        assert self.db.query() == []
+"#;
+    let (_temp, report) = report(&[("test_db.py", src)]);
+    assert!(report.methods.is_empty());
+    let xml = xml(&report, &["test_db.py"]);
+    assert!(xml.contains(
+        r#"<testcase name="setUp" classname="test_db.TestDb" file="test_db.py" line="8">
+      <failure type="PSH015" message="inherited setUp chain is broken: TestDb -&gt; Mixin.setUp"#
+    ));
+    assert!(xml.contains(r#"tests="1" failures="1""#));
+}
+
+#[test]
+fn junit_combines_violations_of_one_method() {
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    async def setUp(self):
+        self.widget = Widget()
+"#;
+    let (_temp, report) = report(&[("test_widget.py", src)]);
+    assert_eq!(report.diagnostics.len(), 2);
+    let xml = xml(&report, &["test_widget.py"]);
+    assert_eq!(xml.matches("<failure ").count(), 1);
+    assert!(xml.contains(r#"<failure type="PSH005,PSH002" message="setUp must not be declared with async def; use asyncSetUp; super().setUp() must be the last line""#));
+}
+
+#[test]
+fn junit_replaces_characters_xml_forbids() {
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setup(self):
+        self.widget = Widget()
+"#;
+    let (_temp, mut report) = report(&[("test_widget.py", src)]);
+    report.diagnostics[0].message = "bad \u{0}byte\u{1b} & \"tab\"\there".to_string();
+    let xml = xml(&report, &["test_widget.py"]);
+    assert!(xml.contains(r#"message="bad �byte� &amp; &quot;tab&quot;	here""#));
+    assert!(!xml
+        .chars()
+        .any(|c| c < ' ' && !matches!(c, '\t' | '\n' | '\r')));
+}
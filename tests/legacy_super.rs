@@ -0,0 +1,202 @@
+// tests/legacy_super.rs
+use pytest_super_hooks::{check_file_with_config, fix::fix_file_with_config, Config};
+use std::fs::{read_to_string, write};
+use tempfile::TempDir;
+
+// Test suite for legacy super forms: super(Cls, self).setUp() and Base.setUp(self)
+
+fn run(src: &str, require_zero_arg_super: bool) -> Vec<String> {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    check_file_with_config(
+        &file_path,
+        &Config {
+            require_zero_arg_super,
+            ..Config::default()
+        },
+    )
+    .iter()
+    .map(ToString::to_string)
+    .collect()
+}
+
+fn run_fix(src: &str, require_zero_arg_super: bool) -> (Vec<String>, String) {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    let config = Config {
+        require_zero_arg_super,
+        ..Config::default()
+    };
+    fix_file_with_config(&file_path, &config);
+    let errors = check_file_with_config(&file_path, &config)
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    (errors, read_to_string(&file_path).unwrap())
+}
+
+#[test]
+fn legacy_super_accepted_by_default() {
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        self.value = 1
+        super(T, self).setUp()
+
+    def tearDown(self):
+        self.cleanup()
+        TestCase.tearDown(self)
+"#;
+    assert!(run(src, false).is_empty());
+}
+
+#[test]
+fn legacy_class_fixture_accepted_by_default() {
+    let src = r#"
+class T(TestCase):
+    @classmethod
+    def setUpClass(cls):
+        cls.db = connect()
+        super(T, cls).setUpClass()
+"#;
+    assert!(run(src, false).is_empty());
+}
+
+#[test]
+fn legacy_super_not_last_reports_last_line() {
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        super(T, self).setUp()
+        self.value = 1
+"#;
+    let errors = run(src, false);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("super().setUp() must be the last line"));
+}
+
+#[test]
+fn legacy_super_flagged_when_required() {
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        self.value = 1
+        super(T, self).setUp()
+"#;
+    let errors = run(src, true);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("use super().setUp() instead of super(T, self).setUp()"));
+}
+
+#[test]
+fn explicit_base_call_flagged_when_required() {
+    let src = r#"
+class T(unittest.TestCase):
+    def tearDown(self):
+        self.cleanup()
+        unittest.TestCase.tearDown(self)
+"#;
+    let errors = run(src, true);
+    assert_eq!(errors.len(), 1);
+    assert!(
+        errors[0].contains("use super().tearDown() instead of unittest.TestCase.tearDown(self)")
+    );
+}
+
+#[test]
+fn legacy_super_naming_other_class() {
+    let src = r#"
+class Base(TestCase):
+    pass
+
+class Derived(Base):
+    def setUp(self):
+        self.value = 1
+        super(Base, self).setUp()
+"#;
+    let errors = run(src, true);
+    assert_eq!(errors.len(), 1);
+    assert!(
+        errors[0].contains("super(Base, self).setUp() does not name the enclosing class Derived")
+    );
+}
+
+#[test]
+fn legacy_async_super_must_be_awaited() {
+    let src = r#"
+class T(IsolatedAsyncioTestCase):
+    async def asyncSetUp(self):
+        self.value = 1
+        super(T, self).asyncSetUp()
+"#;
+    let errors = run(src, false);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("super().asyncSetUp() must be awaited"));
+}
+
+#[test]
+fn fix_modernizes_legacy_super() {
+    let src = r#"class T(TestCase):
+    def setUp(self):
+        self.value = 1
+        super(T, self).setUp()
+
+    async def asyncTearDown(self):
+        self.cleanup()
+        await super(T, self).asyncTearDown()
+"#;
+    let (errors, fixed) = run_fix(src, true);
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert!(fixed.contains("        super().setUp()"));
+    assert!(fixed.contains("        await super().asyncTearDown()"));
+    assert!(!fixed.contains("super(T, self)"));
+}
+
+#[test]
+fn fix_modernizes_explicit_first_base_only() {
+    let src = r#"class T(TestCase, Mixin):
+    def setUp(self):
+        self.value = 1
+        TestCase.setUp(self)
+
+    def tearDown(self):
+        self.cleanup()
+        Mixin.tearDown(self)
+"#;
+    let (errors, fixed) = run_fix(src, true);
+    assert!(fixed.contains("        super().setUp()"));
+    assert!(fixed.contains("        Mixin.tearDown(self)"));
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("use super().tearDown() instead of Mixin.tearDown(self)"));
+}
+
+#[test]
+fn fix_keeps_legacy_super_naming_other_class() {
+    let src = r#"class Base(TestCase):
+    pass
+
+class Derived(Base):
+    def setUp(self):
+        self.value = 1
+        super(Base, self).setUp()
+"#;
+    let (errors, fixed) = run_fix(src, true);
+    assert!(fixed.contains("        super(Base, self).setUp()"));
+    assert!(!fixed.contains("super().setUp()"));
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn fix_moves_legacy_super_to_end_without_duplicating() {
+    let src = r#"class T(TestCase):
+    def setUp(self):
+        super(T, self).setUp()
+        self.value = 1
+"#;
+    let (errors, fixed) = run_fix(src, false);
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert_eq!(fixed.matches("setUp()").count(), 1);
+    assert!(fixed.trim_end().ends_with("super(T, self).setUp()"));
+}
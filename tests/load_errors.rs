@@ -0,0 +1,128 @@
+// tests/load_errors.rs
+use pytest_super_hooks::{
+    check_file, check_file_with_config, check_files_with_config, fix::fix_file, select_rules,
+    Config, Location, Rule, RuleSelector,
+};
+use std::fs::{read, read_to_string, write};
+use std::path::Path;
+use tempfile::TempDir;
+
+// Test suite for files that can't be read, decoded or parsed cleanly
+
+#[test]
+fn syntax_error_is_reported_and_rest_of_file_checked() {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setUp(self):
+        self.widget = Widget()
+
+    def broken(self)
+        pass
+"#;
+    write(&file_path, src).unwrap();
+
+    let diagnostics = check_file(&file_path);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].rule, Rule::SyntaxError);
+    assert_eq!(diagnostics[0].start.line, 8);
+    assert!(diagnostics[0].message.starts_with("syntax error: "));
+    // The recovered module is still checked, but nothing is offered as a fix
+    assert_eq!(diagnostics[1].rule, Rule::SuperPosition);
+    assert_eq!(diagnostics[1].method.as_deref(), Some("setUp"));
+    assert!(diagnostics.iter().all(|d| d.fix.is_none()));
+
+    assert!(!fix_file(&file_path));
+    assert_eq!(read_to_string(&file_path).unwrap(), src);
+}
+
+#[test]
+fn unreadable_file_is_reported() {
+    let temp = TempDir::new().unwrap();
+    let missing = temp.path().join("test_missing.py");
+
+    let diagnostics = check_file(&missing);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].rule, Rule::IoError);
+    assert_eq!(diagnostics[0].start, Location { line: 1, column: 1 });
+    assert!(diagnostics[0].message.starts_with("can't read file: "));
+
+    // Directories can't be read either
+    let diagnostics = check_file(temp.path());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].rule, Rule::IoError);
+}
+
+#[test]
+fn latin1_file_is_reported_and_left_alone() {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    let src = b"from unittest import TestCase\n# caf\xe9\nclass TestWidget(TestCase):\n    def setup(self):\n        super().setUp()\n";
+    write(&file_path, src).unwrap();
+
+    let diagnostics: Vec<String> = check_file(&file_path)
+        .iter()
+        .map(|diagnostic| diagnostic.to_string())
+        .collect();
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics[0].ends_with("test.py:2:6 PSH021 file is not valid UTF-8"));
+    assert!(diagnostics[1].ends_with("test.py:4:9 PSH001 use correct casing: setUp / tearDown"));
+
+    assert!(!fix_file(&file_path));
+    assert_eq!(read(&file_path).unwrap(), src);
+}
+
+#[test]
+fn load_errors_can_be_ignored() {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, "def broken(:\n    pass\n").unwrap();
+
+    let config = Config {
+        rules: select_rules(None, &[], &["PSH019".parse::<RuleSelector>().unwrap()]),
+        ..Config::default()
+    };
+    assert!(check_file_with_config(&file_path, &config).is_empty());
+}
+
+#[test]
+fn imported_files_with_errors_are_not_reported() {
+    let temp = TempDir::new().unwrap();
+    write(
+        temp.path().join("base.py"),
+        r#"
+from unittest import TestCase
+
+class ApiBase(TestCase):
+    def setUp(self):
+        self.client = Client()
+        super().setUp()
+
+def helper(:
+    pass
+"#,
+    )
+    .unwrap();
+    let test_path = temp.path().join("test_api.py");
+    write(
+        &test_path,
+        r#"
+from base import ApiBase
+
+class TestApi(ApiBase):
+    def setUp(self):
+        super().setUp()
+        self.api = Api(self.client)
+"#,
+    )
+    .unwrap();
+
+    // The base class is still found in the recovered module
+    let diagnostics = check_files_with_config(&[Path::new(&test_path)], &Config::default());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].rule, Rule::SuperPosition);
+    assert_eq!(diagnostics[0].file, test_path);
+}
@@ -0,0 +1,141 @@
+// tests/module_fixtures.rs
+use pytest_super_hooks::check_file;
+use std::fs::write;
+use tempfile::TempDir;
+
+// Test suite for unittest module fixtures (setUpModule / tearDownModule)
+
+fn run(src: &str) -> Vec<String> {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    check_file(&file_path)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn module_fixtures_valid() {
+    let src = r#"
+import unittest
+
+def setUpModule():
+    start_server()
+
+def tearDownModule():
+    stop_server()
+"#;
+    assert!(run(src).is_empty());
+}
+
+#[test]
+fn setup_module_with_add_module_cleanup() {
+    let src = r#"
+import unittest
+
+def setUpModule():
+    server = start_server()
+    unittest.addModuleCleanup(server.stop)
+"#;
+    assert!(run(src).is_empty());
+}
+
+#[test]
+fn setup_module_without_teardown() {
+    let src = r#"
+import unittest
+
+def setUpModule():
+    start_server()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("setUpModule has no matching tearDownModule or addModuleCleanup"));
+}
+
+#[test]
+fn setup_module_with_self() {
+    let src = r#"
+import unittest
+
+def setUpModule(self):
+    start_server()
+
+def tearDownModule():
+    stop_server()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("setUpModule must not take a self parameter"));
+}
+
+#[test]
+fn teardown_module_with_arguments() {
+    let src = r#"
+import unittest
+
+def tearDownModule(module):
+    stop_server()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("tearDownModule must not take arguments"));
+}
+
+#[test]
+fn setup_module_inside_class() {
+    let src = r#"
+import unittest
+
+class ServerTest(unittest.TestCase):
+    def setUpModule():
+        start_server()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("setUpModule must be defined at module level"));
+}
+
+#[test]
+fn module_fixture_casing_in_unittest_module() {
+    let src = r#"
+from unittest import TestCase
+
+def setupModule():
+    start_server()
+
+def teardown_module():
+    stop_server()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 2);
+    assert!(errors
+        .iter()
+        .all(|e| e.contains("use correct casing: setUpModule / tearDownModule")));
+}
+
+#[test]
+fn pytest_module_hooks_left_alone_outside_unittest() {
+    let src = r#"
+import pytest
+
+def setup_module(module):
+    start_server()
+
+def teardown_module(module):
+    stop_server()
+"#;
+    assert!(run(src).is_empty());
+}
+
+#[test]
+fn unittest_mock_import_is_not_unittest() {
+    let src = r#"
+from unittest.mock import patch
+
+def setup_module(module):
+    start_server()
+"#;
+    assert!(run(src).is_empty());
+}
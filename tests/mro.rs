@@ -0,0 +1,218 @@
+// tests/mro.rs
+use pytest_super_hooks::check_file;
+use std::fs::{create_dir_all, write};
+use tempfile::TempDir;
+
+// Test suite for following lifecycle chains through the MRO of classes with mixins
+
+fn run(src: &str) -> Vec<String> {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    check_file(&file_path)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn cooperative_mixins_accepted() {
+    let src = r#"
+from unittest import TestCase
+
+class DbMixin:
+    def setUp(self):
+        self.db = connect()
+        super().setUp()
+
+class CacheMixin:
+    def setUp(self):
+        self.cache = {}
+        super().setUp()
+
+class T(DbMixin, CacheMixin, TestCase):
+    def setUp(self):
+        self.value = 1
+        super().setUp()
+"#;
+    assert!(run(src).is_empty());
+}
+
+#[test]
+fn mixin_ending_chain_before_test_case() {
+    let src = r#"
+from unittest import TestCase
+
+class DbMixin:
+    def setUp(self):
+        self.db = connect()
+
+class CacheMixin:
+    def setUp(self):
+        self.cache = {}
+        super().setUp()
+
+class T(DbMixin, CacheMixin, TestCase):
+    pass
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("inherited setUp chain is broken: T -> DbMixin.setUp ("));
+    assert!(errors[0].contains(
+        "test.py:5) never calls super().setUp(), so CacheMixin.setUp and unittest.TestCase.setUp never run"
+    ));
+}
+
+#[test]
+fn last_mixin_ending_chain_skips_test_case() {
+    let src = r#"
+from unittest import TestCase
+
+class DbMixin:
+    def tearDown(self):
+        self.db.close()
+        super().tearDown()
+
+class CacheMixin:
+    def tearDown(self):
+        self.cache.clear()
+
+class T(DbMixin, CacheMixin, TestCase):
+    pass
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("T -> DbMixin -> CacheMixin.tearDown ("));
+    assert!(errors[0].contains("so unittest.TestCase.tearDown never runs"));
+}
+
+#[test]
+fn mixin_after_test_case_unreachable() {
+    let src = r#"
+from unittest import TestCase
+
+class DbMixin:
+    def setUp(self):
+        self.db = connect()
+        super().setUp()
+
+class T(TestCase, DbMixin):
+    def setUp(self):
+        self.value = 1
+        super().setUp()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("DbMixin.setUp ("));
+    assert!(errors[0].contains(
+        "test.py:5) is unreachable: the MRO T -> unittest.TestCase -> DbMixin reaches unittest.TestCase.setUp first, which never calls super().setUp(); list DbMixin before unittest.TestCase in the bases"
+    ));
+}
+
+#[test]
+fn mixin_after_project_base_unreachable() {
+    let src = r#"
+from django.test import TestCase
+
+class ApiBase(TestCase):
+    def setUp(self):
+        self.client = Client()
+        super().setUp()
+
+class DbMixin:
+    def setUp(self):
+        self.db = connect()
+        super().setUp()
+
+class T(ApiBase, DbMixin):
+    pass
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("the MRO T -> ApiBase -> django.test.TestCase -> DbMixin"));
+}
+
+#[test]
+fn diamond_follows_c3_order() {
+    let src = r#"
+from unittest import TestCase
+
+class Base(TestCase):
+    def setUp(self):
+        self.base = True
+        super().setUp()
+
+class Left(Base):
+    def setUp(self):
+        self.left = True
+
+class Right(Base):
+    def setUp(self):
+        self.right = True
+        super().setUp()
+
+class T(Left, Right):
+    pass
+"#;
+    let errors = run(src);
+    assert!(errors.iter().any(|error| error
+        .contains("inherited setUp chain is broken: T -> Left.setUp (")
+        && error.contains("so Right.setUp, Base.setUp and unittest.TestCase.setUp never run")));
+}
+
+#[test]
+fn mixin_without_lifecycle_methods_ignored() {
+    let src = r#"
+from unittest import TestCase
+
+class HelpersMixin:
+    def make_user(self):
+        return User()
+
+class T(TestCase, HelpersMixin):
+    def setUp(self):
+        self.user = self.make_user()
+        super().setUp()
+"#;
+    assert!(run(src).is_empty());
+}
+
+#[test]
+fn inconsistent_mro_skipped() {
+    let src = r#"
+from unittest import TestCase
+
+class Base(TestCase):
+    pass
+
+class T(TestCase, Base):
+    pass
+"#;
+    assert!(run(src).is_empty());
+}
+
+#[test]
+fn mixin_from_other_file() {
+    let temp = TempDir::new().unwrap();
+    create_dir_all(temp.path().join("support")).unwrap();
+    write(temp.path().join("support/__init__.py"), "").unwrap();
+    write(
+        temp.path().join("support/mixins.py"),
+        "class DbMixin:\n    async def asyncSetUp(self):\n        self.db = await connect()\n",
+    )
+    .unwrap();
+    let test_path = temp.path().join("test_db.py");
+    write(
+        &test_path,
+        "from unittest import IsolatedAsyncioTestCase\nfrom support.mixins import DbMixin\n\nclass T(DbMixin, IsolatedAsyncioTestCase):\n    pass\n",
+    )
+    .unwrap();
+
+    let errors = check_file(&test_path)
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("T -> DbMixin.asyncSetUp ("));
+    assert!(errors[0].contains("mixins.py:2) never calls await super().asyncSetUp()"));
+}
@@ -8,27 +8,32 @@ fn run(src: &str) -> Vec<String> {
     let file_path = temp.path().join("test.py");
     write(&file_path, src).unwrap();
     check_file(&file_path)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
 }
 
 #[test]
 fn empty_class() {
-    assert!(run("class T:\n    pass\n").is_empty());
+    assert!(run("class T(TestCase):\n    pass\n").is_empty());
 }
 
 #[test]
 fn setup_only_valid() {
-    assert!(run("class T:\n    def setUp(self):\n        super().setUp()\n").is_empty());
+    assert!(run("class T(TestCase):\n    def setUp(self):\n        super().setUp()\n").is_empty());
 }
 
 #[test]
 fn teardown_only_valid() {
-    assert!(run("class T:\n    def tearDown(self):\n        super().tearDown()\n").is_empty());
+    assert!(
+        run("class T(TestCase):\n    def tearDown(self):\n        super().tearDown()\n").is_empty()
+    );
 }
 
 #[test]
 fn setup_missing_super() {
     assert_eq!(
-        run("class T:\n    def setUp(self):\n        x=1\n").len(),
+        run("class T(TestCase):\n    def setUp(self):\n        x=1\n").len(),
         1
     );
 }
@@ -36,7 +41,7 @@ fn setup_missing_super() {
 #[test]
 fn teardown_missing_super() {
     assert_eq!(
-        run("class T:\n    def tearDown(self):\n        x=1\n").len(),
+        run("class T(TestCase):\n    def tearDown(self):\n        x=1\n").len(),
         1
     );
 }
@@ -44,7 +49,8 @@ fn teardown_missing_super() {
 #[test]
 fn setup_super_not_last() {
     assert_eq!(
-        run("class T:\n    def setUp(self):\n        super().setUp()\n        x=1\n").len(),
+        run("class T(TestCase):\n    def setUp(self):\n        super().setUp()\n        x=1\n")
+            .len(),
         1
     );
 }
@@ -52,7 +58,7 @@ fn setup_super_not_last() {
 #[test]
 fn teardown_super_not_last() {
     assert_eq!(
-        run("class T:\n    def tearDown(self):\n        super().tearDown()\n        x=1\n").len(),
+        run("class T(TestCase):\n    def tearDown(self):\n        super().tearDown()\n        x=1\n").len(),
         1
     );
 }
@@ -60,7 +66,7 @@ fn teardown_super_not_last() {
 #[test]
 fn bad_setup_casing() {
     assert_eq!(
-        run("class T:\n    def setup(self):\n        pass\n").len(),
+        run("class T(TestCase):\n    def setup(self):\n        pass\n").len(),
         1
     );
 }
@@ -68,7 +74,7 @@ fn bad_setup_casing() {
 #[test]
 fn bad_teardown_casing() {
     assert_eq!(
-        run("class T:\n    def teardown(self):\n        pass\n").len(),
+        run("class T(TestCase):\n    def teardown(self):\n        pass\n").len(),
         1
     );
 }
@@ -77,7 +83,7 @@ fn bad_teardown_casing() {
 fn mixed_good_and_bad() {
     assert_eq!(
         run(
-            "class T:\n    def setUp(self):\n        super().setUp()\n    def teardown(self):\n        super().tearDown()\n"
+            "class T(TestCase):\n    def setUp(self):\n        super().setUp()\n    def teardown(self):\n        super().tearDown()\n"
         )
         .len(),
         1
@@ -86,34 +92,33 @@ fn mixed_good_and_bad() {
 
 #[test]
 fn nested_function_ignored() {
-    assert!(run("class T:\n\
-         def setUp(self):\n\
-             def inner(): pass\n\
-             super().setUp()\n")
+    assert!(run(
+        "class T(TestCase):\n    def setUp(self):\n        def inner(): pass\n        super().setUp()\n"
+    )
     .is_empty());
 }
 
 #[test]
 fn async_setup() {
-    assert!(run("class T:\n\
-         async def setUp(self):\n\
-             super().setUp()\n")
-    .is_empty());
+    // unittest never awaits setUp, so an async one is flagged
+    assert_eq!(
+        run("class T(TestCase):\n    async def setUp(self):\n        super().setUp()\n").len(),
+        1
+    );
 }
 
 #[test]
 fn decorator_on_teardown() {
-    assert!(run("class T:\n\
-         @foo\n\
-         def tearDown(self):\n\
-             super().tearDown()\n")
+    assert!(run(
+        "class T(TestCase):\n    @foo\n    def tearDown(self):\n        super().tearDown()\n"
+    )
     .is_empty());
 }
 
 #[test]
 fn wrong_super_target() {
     assert_eq!(
-        run("class T:\n    def tearDown(self):\n        super().setUp()\n").len(),
+        run("class T(TestCase):\n    def tearDown(self):\n        super().setUp()\n").len(),
         1
     );
 }
@@ -121,7 +126,7 @@ fn wrong_super_target() {
 #[test]
 fn attribute_after_super_call() {
     assert_eq!(
-        run("class T:\n    def tearDown(self):\n        super().tearDown()\n        self.x = 1\n")
+        run("class T(TestCase):\n    def tearDown(self):\n        super().tearDown()\n        self.x = 1\n")
             .len(),
         1
     );
@@ -131,7 +136,7 @@ fn attribute_after_super_call() {
 fn multiple_classes() {
     assert_eq!(
         run(
-            "class A:\n    def tearDown(self):\n        super().tearDown()\n    class B:\n        def setup(self): pass\n"
+            "class A(TestCase):\n    def tearDown(self):\n        super().tearDown()\n    class B(TestCase):\n        def setup(self): pass\n"
         )
         .len(),
         1
@@ -140,31 +145,30 @@ fn multiple_classes() {
 
 #[test]
 fn unrelated_function_named_teardown() {
-    assert_eq!(run("def teardown():\n    pass\n").len(), 1);
+    assert!(run("def teardown():\n    pass\n").is_empty());
 }
 
 #[test]
 fn docstring_before_super() {
-    assert!(run("class T:\n\
-         def tearDown(self):\n\
-             \"doc\"\n\
-             super().tearDown()\n")
+    assert!(run(
+        "class T(TestCase):\n    def tearDown(self):\n        \"doc\"\n        super().tearDown()\n"
+    )
     .is_empty());
 }
 
 #[test]
 fn return_after_super() {
     assert_eq!(
-        run("class T:\n    def setUp(self):\n        super().setUp()\n        return\n").len(),
+        run("class T(TestCase):\n    def setUp(self):\n        super().setUp()\n        return\n")
+            .len(),
         1
     );
 }
 
 #[test]
 fn comments_only_after_super() {
-    assert!(run("class T:\n\
-         def tearDown(self):\n\
-             super().tearDown()\n\
-             # comment\n")
+    assert!(run(
+        "class T(TestCase):\n    def tearDown(self):\n        super().tearDown()\n        # comment\n"
+    )
     .is_empty());
 }
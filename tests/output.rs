@@ -0,0 +1,352 @@
+// tests/output.rs
+use pytest_super_hooks::{
+    check_file, check_file_with_config,
+    output::{
+        code_frame, github, gitlab, json, json_lines, sarif, OutputFormat, Run, JSON_SCHEMA_VERSION,
+    },
+    Config, Diagnostic,
+};
+use std::{fs::write, path::Path};
+use tempfile::TempDir;
+
+// Test suite for the output formats: code frames, the JSON schema and CI annotations
+
+fn run(src: &str, config: &Config) -> Vec<Diagnostic> {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    check_file_with_config(&file_path, config)
+        .into_iter()
+        .map(|mut diagnostic| {
+            diagnostic.file = "test.py".into();
+            diagnostic
+        })
+        .collect()
+}
+
+#[test]
+fn frame_underlines_violation_and_labels_call() {
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setUp(self):
+        super().setUp()
+        self.widget = Widget()
+"#;
+    let diagnostics = run(src, &Config::default());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        code_frame(&diagnostics[0], src, false),
+        r#"error[PSH002]: super().setUp() must be the last line
+ --> test.py:7:9
+  |
+6 |         super().setUp()
+  |         --------------- super().setUp() found here
+7 |         self.widget = Widget()
+  |         ^^^^^^^^^^^^^^^^^^^^^^
+  |
+  = help: Move super().setUp() to the end
+"#
+    );
+}
+
+#[test]
+fn frame_elides_distant_lines() {
+    let src = r#"
+from unittest import TestCase
+
+class ApiBase(TestCase):
+    def setUp(self):
+        self.client = Client()
+        super().setUp()
+
+
+class TestApi(ApiBase):
+    def setUp(self):
+        self.client = FakeClient()
+        super().setUp()
+"#;
+    let diagnostics = run(src, &Config::default());
+    assert_eq!(diagnostics.len(), 1);
+    let frame = code_frame(&diagnostics[0], src, false);
+    assert!(frame.contains(
+        " 6 |         self.client = Client()\n   |         ----------- ApiBase.setUp assigns it here\n...\n12 |         self.client = FakeClient()\n   |         ^^^^^^^^^^^\n"
+    ));
+    // Violations without a fix have no help line
+    assert!(!frame.contains("help:"));
+}
+
+#[test]
+fn frame_offers_only_fixes_with_edits() {
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setUp(self):
+        super().setUp()
+        self.widget = Widget()
+"#;
+    let mut diagnostics = run(src, &Config::default());
+    assert!(code_frame(&diagnostics[0], src, false).contains("= help: "));
+    diagnostics[0].fix.as_mut().unwrap().edits.clear();
+    assert!(!code_frame(&diagnostics[0], src, false).contains("help:"));
+}
+
+#[test]
+fn frame_without_source_keeps_header() {
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setup(self):
+        self.widget = Widget()
+"#;
+    let diagnostics = run(src, &Config::default());
+    assert_eq!(
+        code_frame(&diagnostics[0], "", false),
+        "error[PSH001]: use correct casing: setUp / tearDown\n --> test.py:5:9\n  = help: Rename to setUp\n"
+    );
+}
+
+#[test]
+fn frame_colors_only_on_request() {
+    let src = r#"
+import unittest
+
+def setUpModule():
+    connect()
+"#;
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    let diagnostics = check_file(&file_path);
+    assert_eq!(diagnostics.len(), 1);
+    let plain = code_frame(&diagnostics[0], src, false);
+    assert!(plain.starts_with("warning[PSH011]: "));
+    assert!(!plain.contains('\x1b'));
+    let colored = code_frame(&diagnostics[0], src, true);
+    assert!(colored.starts_with("\x1b[1;33mwarning[PSH011]\x1b[0m"));
+}
+
+#[test]
+fn output_format_parses() {
+    assert_eq!("full".parse(), Ok(OutputFormat::Full));
+    assert_eq!("concise".parse(), Ok(OutputFormat::Concise));
+    assert_eq!("jsonl".parse(), Ok(OutputFormat::JsonLines));
+    assert_eq!(
+        "fancy".parse::<OutputFormat>().unwrap_err(),
+        "invalid output format 'fancy': expected concise, full, json, jsonl, sarif, github, gitlab or junit"
+    );
+}
+
+#[test]
+fn json_document_lists_run_and_diagnostics() {
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setUp(self):
+        super().setUp()
+        self.widget = Widget()
+"#;
+    let diagnostics = run(src, &Config::default());
+    let files_fixed = vec!["test_fixed.py".into()];
+    let output = json(&Run {
+        files_checked: &[Path::new("test.py"), Path::new("test_fixed.py")],
+        files_fixed: &files_fixed,
+        diagnostics: &diagnostics,
+    });
+    let document: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(
+        document,
+        serde_json::json!({
+            "schema_version": JSON_SCHEMA_VERSION,
+            "tool": {"name": "pytest-super-hooks", "version": env!("CARGO_PKG_VERSION")},
+            "files_checked": ["test.py", "test_fixed.py"],
+            "files_fixed": ["test_fixed.py"],
+            "diagnostics": [{
+                "code": "PSH002",
+                "name": "super-position",
+                "severity": "error",
+                "message": "super().setUp() must be the last line",
+                "file": "test.py",
+                "span": {"start": {"line": 7, "column": 9}, "end": {"line": 7, "column": 31}},
+                "labels": [{
+                    "span": {"start": {"line": 6, "column": 9}, "end": {"line": 6, "column": 24}},
+                    "message": "super().setUp() found here"
+                }],
+                "fixable": true,
+                "fix": "Move super().setUp() to the end",
+                "class": "TestWidget",
+                "method": "setUp"
+            }]
+        })
+    );
+}
+
+#[test]
+fn json_lines_end_with_run() {
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setup(self):
+        self.widget = Widget()
+
+    def tearDown(self):
+        pass
+
+    def setUpClass(cls):
+        pass
+"#;
+    let diagnostics = run(src, &Config::default());
+    let output = json_lines(&Run {
+        files_checked: &[Path::new("test.py")],
+        files_fixed: &[],
+        diagnostics: &diagnostics,
+    });
+    let lines: Vec<serde_json::Value> = output
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0]["type"], "diagnostic");
+    assert_eq!(lines[0]["code"], "PSH001");
+    assert_eq!(lines[1]["code"], "PSH003");
+    assert_eq!(lines[1]["fixable"], false);
+    assert_eq!(lines[2]["type"], "run");
+    assert_eq!(lines[2]["schema_version"], JSON_SCHEMA_VERSION);
+    assert_eq!(lines[2]["files_checked"], serde_json::json!(["test.py"]));
+
+    // Clean runs still report the run
+    let output = json_lines(&Run {
+        files_checked: &[Path::new("test.py")],
+        files_fixed: &[],
+        diagnostics: &[],
+    });
+    assert_eq!(output.lines().count(), 1);
+}
+
+#[test]
+fn json_fixable_agrees_with_sarif_fixes() {
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setUp(self):
+        if self.fast:
+            return
+        super().setUp()
+
+    def tearDown(self):
+        super().tearDown()
+        self.widget.close()
+"#;
+    let mut diagnostics = run(src, &Config::default());
+    assert_eq!(diagnostics.len(), 2);
+    // A fix whose edits didn't come through offers nothing either
+    let mut without_edits = diagnostics[1].clone();
+    without_edits.fix.as_mut().unwrap().edits.clear();
+    diagnostics.push(without_edits);
+    let run = Run {
+        files_checked: &[Path::new("test.py")],
+        files_fixed: &[],
+        diagnostics: &diagnostics,
+    };
+    let lines: Vec<serde_json::Value> = json_lines(&run)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let log: serde_json::Value = serde_json::from_str(&sarif(&run)).unwrap();
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    // Moving the call to the end doesn't help the early return, so only the tearDown has a
+    // fix to apply
+    assert_eq!(lines[0]["fixable"], false);
+    assert_eq!(lines[0]["fix"], serde_json::Value::Null);
+    assert_eq!(lines[1]["fixable"], true);
+    assert_eq!(lines[1]["fix"], "Move super().tearDown() to the end");
+    assert_eq!(lines[2]["fixable"], false);
+    assert_eq!(lines[2]["fix"], serde_json::Value::Null);
+    for (line, result) in lines.iter().zip(results) {
+        assert_eq!(line["fixable"], result.get("fixes").is_some());
+    }
+}
+
+#[test]
+fn github_prints_workflow_commands() {
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setUp(self):
+        super().setUp()
+        self.widget = Widget()
+
+def setUpModule():
+    connect()
+"#;
+    let diagnostics = run(src, &Config::default());
+    let output = github(&Run {
+        files_checked: &[Path::new("test.py")],
+        files_fixed: &[],
+        diagnostics: &diagnostics,
+    });
+    assert_eq!(
+        output,
+        "::error file=test.py,line=7,col=9,endLine=7,endColumn=31,title=PSH002 (super-position)::super().setUp() must be the last line\n\
+         ::warning file=test.py,line=9,col=1,endLine=10,endColumn=14,title=PSH011 (missing-module-teardown)::setUpModule has no matching tearDownModule or addModuleCleanup\n"
+    );
+}
+
+#[test]
+fn gitlab_fingerprints_survive_line_shifts() {
+    let src = r#"
+from unittest import TestCase
+
+class ApiBase(TestCase):
+    def setUp(self):
+        self.client = Client()
+        super().setUp()
+
+class TestApi(ApiBase):
+    def setUp(self):
+        self.client = FakeClient()
+        super().setUp()
+
+    def tearDown(self):
+        self.client.close()
+"#;
+    // The messages name the file, so both versions are checked at the same path
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    let report = |src: &str| -> serde_json::Value {
+        write(&file_path, src).unwrap();
+        let diagnostics = check_file(&file_path);
+        serde_json::from_str(&gitlab(&Run {
+            files_checked: &[&file_path],
+            files_fixed: &[],
+            diagnostics: &diagnostics,
+        }))
+        .unwrap()
+    };
+    let before = report(src);
+    let issues = before.as_array().unwrap();
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0]["check_name"], "PSH018");
+    assert_eq!(issues[0]["severity"], "major");
+    assert!(issues[0]["location"]["path"]
+        .as_str()
+        .unwrap()
+        .ends_with("/test.py"));
+    assert_eq!(issues[0]["location"]["lines"]["begin"], 11);
+    assert_ne!(issues[0]["fingerprint"], issues[1]["fingerprint"]);
+
+    // Moving everything down, including the ancestor's assignment the message points at,
+    // keeps the fingerprints
+    let shifted = report(&src.replacen("\nclass ApiBase", "\n\n\nclass ApiBase", 1));
+    assert_eq!(shifted[0]["location"]["lines"]["begin"], 13);
+    assert_ne!(shifted[0]["description"], issues[0]["description"]);
+    assert_eq!(shifted[0]["fingerprint"], issues[0]["fingerprint"]);
+    assert_eq!(shifted[1]["fingerprint"], issues[1]["fingerprint"]);
+}
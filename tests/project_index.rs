@@ -0,0 +1,180 @@
+// tests/project_index.rs
+use pytest_super_hooks::{check_file, check_files_with_config, fix::fix_file, Config};
+use std::fs::{create_dir_all, read_to_string, write};
+use tempfile::TempDir;
+
+// Test suite for resolving test class bases across the files of a project
+
+/// Write `files` (relative path, source) into a fresh project directory.
+fn project(files: &[(&str, &str)]) -> TempDir {
+    let temp = TempDir::new().unwrap();
+    for (path, src) in files {
+        let path = temp.path().join(path);
+        create_dir_all(path.parent().unwrap()).unwrap();
+        write(&path, src).unwrap();
+    }
+    temp
+}
+
+const BASE: &str = r#"
+from unittest import TestCase
+
+class ApiBase(TestCase):
+    def setUp(self):
+        self.client = Client()
+        super().setUp()
+"#;
+
+const SUBCLASS: &str = r#"
+class T(ApiBase):
+    def setUp(self):
+        self.value = 1
+"#;
+
+#[test]
+fn base_class_in_sibling_module() {
+    let temp = project(&[
+        ("base.py", BASE),
+        (
+            "test_api.py",
+            &format!("from base import ApiBase\n{}", SUBCLASS),
+        ),
+    ]);
+    let errors = check_file(&temp.path().join("test_api.py"))
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("super().setUp() must be the last line"));
+}
+
+#[test]
+fn base_class_through_relative_import() {
+    let temp = project(&[
+        ("tests/__init__.py", ""),
+        ("tests/base.py", BASE),
+        ("tests/api/__init__.py", ""),
+        (
+            "tests/api/test_views.py",
+            &format!("from ..base import ApiBase\n{}", SUBCLASS),
+        ),
+    ]);
+    assert_eq!(
+        check_file(&temp.path().join("tests/api/test_views.py")).len(),
+        1
+    );
+}
+
+#[test]
+fn base_class_reexported_by_package() {
+    let temp = project(&[
+        ("tests/__init__.py", ""),
+        ("tests/support/__init__.py", "from .base import ApiBase\n"),
+        ("tests/support/base.py", BASE),
+        (
+            "tests/test_api.py",
+            &format!("from tests.support import ApiBase\n{}", SUBCLASS),
+        ),
+    ]);
+    assert_eq!(check_file(&temp.path().join("tests/test_api.py")).len(), 1);
+}
+
+#[test]
+fn base_class_through_module_attribute() {
+    let temp = project(&[
+        ("tests/__init__.py", ""),
+        ("tests/base.py", BASE),
+        (
+            "tests/test_api.py",
+            "from tests import base\n\nclass T(base.ApiBase):\n    def setUp(self):\n        self.value = 1\n",
+        ),
+    ]);
+    assert_eq!(check_file(&temp.path().join("tests/test_api.py")).len(), 1);
+}
+
+#[test]
+fn helper_base_in_other_module_not_flagged() {
+    let temp = project(&[
+        ("pages.py", "class Page:\n    def setup(self):\n        pass\n"),
+        (
+            "test_pages.py",
+            "from pages import Page\n\nclass LoginPage(Page):\n    def setup(self):\n        self.open()\n",
+        ),
+    ]);
+    assert!(check_file(&temp.path().join("test_pages.py")).is_empty());
+}
+
+#[test]
+fn chain_across_several_modules() {
+    let temp = project(&[
+        ("base.py", BASE),
+        (
+            "db.py",
+            "from base import ApiBase\n\nclass DbBase(ApiBase):\n    pass\n",
+        ),
+        (
+            "test_db.py",
+            &format!("from db import DbBase as ApiBase\n{}", SUBCLASS),
+        ),
+    ]);
+    assert_eq!(check_file(&temp.path().join("test_db.py")).len(), 1);
+}
+
+#[test]
+fn only_given_files_are_reported() {
+    let broken_base = "from unittest import TestCase\n\nclass ApiBase(TestCase):\n    def setup(self):\n        pass\n";
+    let temp = project(&[
+        ("base.py", broken_base),
+        (
+            "test_api.py",
+            &format!("from base import ApiBase\n{}", SUBCLASS),
+        ),
+    ]);
+    let test_api = temp.path().join("test_api.py");
+    let base = temp.path().join("base.py");
+
+    let errors = check_files_with_config(&[test_api.as_path()], &Config::default())
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(errors.len(), 1);
+
+    let errors = check_files_with_config(&[test_api.as_path(), base.as_path()], &Config::default())
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(errors.len(), 2);
+    assert!(errors[1].contains("use correct casing: setUp / tearDown"));
+}
+
+#[test]
+fn reexport_cycle_terminates() {
+    let temp = project(&[
+        ("pkg/__init__.py", "from .a import ApiBase\n"),
+        ("pkg/a.py", "from . import ApiBase\n"),
+        (
+            "test_cycle.py",
+            &format!("from pkg import ApiBase\n{}", SUBCLASS),
+        ),
+    ]);
+    assert!(check_file(&temp.path().join("test_cycle.py")).is_empty());
+}
+
+#[test]
+fn fix_uses_base_class_from_other_module() {
+    let temp = project(&[
+        ("base.py", BASE),
+        (
+            "test_api.py",
+            &format!("from base import ApiBase\n{}", SUBCLASS),
+        ),
+    ]);
+    let test_api = temp.path().join("test_api.py");
+
+    assert!(fix_file(&test_api));
+    assert!(read_to_string(&test_api)
+        .unwrap()
+        .contains("        self.value = 1\n        super().setUp()"));
+    assert!(check_file(&test_api).is_empty());
+    assert_eq!(read_to_string(temp.path().join("base.py")).unwrap(), BASE);
+}
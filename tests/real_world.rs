@@ -2,13 +2,16 @@ use pytest_super_hooks::check_file;
 use std::fs::write;
 use tempfile::TempDir;
 
-/// Test suite with real-world Python test class examples
+// Test suite with real-world Python test class examples
 
 fn run(src: &str) -> Vec<String> {
     let temp = TempDir::new().unwrap();
     let file_path = temp.path().join("test.py");
     write(&file_path, src).unwrap();
     check_file(&file_path)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
 }
 
 #[test]
@@ -253,7 +256,10 @@ class EarlyReturnTest(TestCase):
             return
         super().setUp()
 "#;
-    assert!(run(src).is_empty());
+    // The early return skips the parent's setUp
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("it is skipped when `if self.skip_setup` is true (line 4)"));
 }
 
 #[test]
@@ -494,8 +500,12 @@ class FinalTestCase(MiddleTestCase):
         super().setUp()
 "#;
     let errors = run(src);
-    assert_eq!(errors.len(), 1);
+    assert_eq!(errors.len(), 2);
     assert!(errors[0].contains("must be the last line"));
+    // The leaf class reports the broken chain it inherits
+    assert!(errors[1]
+        .contains("inherited setUp chain is broken: FinalTestCase -> MiddleTestCase.setUp ("));
+    assert!(errors[1].contains("test.py:8) never calls super().setUp()"));
 }
 
 #[test]
@@ -0,0 +1,173 @@
+// tests/robust_teardown.rs
+use pytest_super_hooks::{check_file_with_config, fix::fix_file_with_config, Config};
+use std::fs::{read_to_string, write};
+use tempfile::TempDir;
+
+// Test suite for the opt-in tearDown robustness rule (super() must run even if cleanup raises)
+
+fn config(require_robust_teardown: bool) -> Config {
+    Config {
+        require_robust_teardown,
+        ..Config::default()
+    }
+}
+
+fn run(src: &str, require_robust_teardown: bool) -> Vec<String> {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    check_file_with_config(&file_path, &config(require_robust_teardown))
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+fn run_fix(src: &str) -> (Vec<String>, String) {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    fix_file_with_config(&file_path, &config(true));
+    let errors = check_file_with_config(&file_path, &config(true))
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    (errors, read_to_string(&file_path).unwrap())
+}
+
+#[test]
+fn raising_cleanup_accepted_by_default() {
+    let src = r#"
+class T(TestCase):
+    def tearDown(self):
+        shutil.rmtree(self.tmp)
+        super().tearDown()
+"#;
+    assert!(run(src, false).is_empty());
+}
+
+#[test]
+fn raising_cleanup_flagged() {
+    let src = r#"
+class T(TestCase):
+    def tearDown(self):
+        self.tmp = None
+        shutil.rmtree(self.path)
+        super().tearDown()
+"#;
+    let errors = run(src, true);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains(
+        "super().tearDown() is skipped if `shutil.rmtree(self.path)` (line 5) raises; call it in a `finally:` block"
+    ));
+}
+
+#[test]
+fn super_in_finally_accepted() {
+    let src = r#"
+class T(TestCase):
+    def tearDown(self):
+        try:
+            shutil.rmtree(self.tmp)
+        finally:
+            super().tearDown()
+
+    @classmethod
+    def tearDownClass(cls):
+        try:
+            cls.server.stop()
+        finally:
+            super().tearDownClass()
+"#;
+    assert!(run(src, true).is_empty());
+}
+
+#[test]
+fn non_raising_cleanup_accepted() {
+    let src = r#"
+class T(TestCase):
+    def tearDown(self):
+        self.tmp = None
+        self.items = []
+        super().tearDown()
+"#;
+    assert!(run(src, true).is_empty());
+}
+
+#[test]
+fn cleanup_guarded_by_catch_all_accepted() {
+    let src = r#"
+class T(TestCase):
+    def tearDown(self):
+        try:
+            shutil.rmtree(self.tmp)
+        except Exception:
+            log.exception("cleanup failed")
+        super().tearDown()
+"#;
+    assert!(run(src, true).is_empty());
+}
+
+#[test]
+fn async_teardown_flagged() {
+    let src = r#"
+class T(IsolatedAsyncioTestCase):
+    async def asyncTearDown(self):
+        await self.client.close()
+        await super().asyncTearDown()
+"#;
+    let errors = run(src, true);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0]
+        .contains("await super().asyncTearDown() is skipped if `await self.client.close()`"));
+}
+
+#[test]
+fn setup_not_affected() {
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        self.tmp = tempfile.mkdtemp()
+        super().setUp()
+"#;
+    assert!(run(src, true).is_empty());
+}
+
+#[test]
+fn fix_wraps_cleanup_in_try_finally() {
+    let src = r#"class T(TestCase):
+    def tearDown(self):
+        """Remove the scratch directory."""
+        shutil.rmtree(self.tmp)
+
+        self.tmp = None
+        super().tearDown()
+"#;
+    let (errors, fixed) = run_fix(src);
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert_eq!(
+        fixed,
+        r#"class T(TestCase):
+    def tearDown(self):
+        """Remove the scratch directory."""
+        try:
+            shutil.rmtree(self.tmp)
+
+            self.tmp = None
+        finally:
+            super().tearDown()
+"#
+    );
+}
+
+#[test]
+fn fix_wraps_teardown_missing_super() {
+    let src = r#"class T(TestCase):
+    def tearDown(self):
+        self.server.stop()
+"#;
+    let (errors, fixed) = run_fix(src);
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert!(fixed.contains(
+        "        try:\n            self.server.stop()\n        finally:\n            super().tearDown()"
+    ));
+}
@@ -0,0 +1,169 @@
+// tests/rule_selection.rs
+use pytest_super_hooks::{
+    check_file_with_config, fix::fix_file_with_config, select_rules, Config, Rule, RuleSelector,
+    RULES,
+};
+use std::fs::{read_to_string, write};
+use tempfile::TempDir;
+
+// Test suite for rule codes, the rule registry and --select / --extend-select / --ignore
+
+fn selectors(codes: &[&str]) -> Vec<RuleSelector> {
+    codes.iter().map(|code| code.parse().unwrap()).collect()
+}
+
+fn run(src: &str, config: &Config) -> Vec<String> {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    check_file_with_config(&file_path, config)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn registry_codes_are_unique_and_round_trip() {
+    assert_eq!(RULES.len(), 21);
+    for (index, info) in RULES.iter().enumerate() {
+        assert_eq!(info.code, format!("PSH{:03}", index + 1));
+        assert_eq!(info.rule.code(), info.code);
+        assert_eq!(Rule::from_code(&info.code.to_lowercase()), Some(info.rule));
+        assert!(!info.explanation.is_empty());
+    }
+    assert_eq!(Rule::from_code("PSH999"), None);
+}
+
+#[test]
+fn default_rules_exclude_opt_in_rules() {
+    let rules = select_rules(None, &[], &[]);
+    assert!(rules.contains(&Rule::SuperPosition));
+    assert!(!rules.contains(&Rule::LegacySuper));
+    assert!(!rules.contains(&Rule::FragileTeardown));
+    assert_eq!(rules.len(), 19);
+}
+
+#[test]
+fn select_by_prefix_and_all() {
+    // PSH010 to PSH019, plus the load errors PSH020 and PSH021, which `--select` keeps
+    let rules = select_rules(Some(&selectors(&["psh01"])), &[], &[]);
+    assert_eq!(rules.len(), 12);
+    assert!(rules.contains(&Rule::ModuleFixtureSignature));
+    assert!(rules.contains(&Rule::DecodeError));
+    assert!(!rules.contains(&Rule::MethodCasing));
+
+    let rules = select_rules(Some(&selectors(&["ALL"])), &[], &[]);
+    assert_eq!(rules.len(), RULES.len());
+}
+
+#[test]
+fn ignore_wins_over_select() {
+    let rules = select_rules(
+        Some(&selectors(&["PSH00"])),
+        &selectors(&["PSH007"]),
+        &selectors(&["PSH007", "PSH002"]),
+    );
+    assert!(rules.contains(&Rule::MethodCasing));
+    assert!(!rules.contains(&Rule::LegacySuper));
+    assert!(!rules.contains(&Rule::SuperPosition));
+}
+
+#[test]
+fn select_keeps_load_errors() {
+    let rules = select_rules(Some(&selectors(&["PSH002"])), &[], &[]);
+    assert_eq!(
+        rules,
+        [
+            Rule::SuperPosition,
+            Rule::SyntaxError,
+            Rule::IoError,
+            Rule::DecodeError
+        ]
+        .into_iter()
+        .collect()
+    );
+
+    let rules = select_rules(Some(&selectors(&["PSH002"])), &[], &selectors(&["PSH02"]));
+    assert_eq!(
+        rules,
+        [Rule::SyntaxError, Rule::SuperPosition]
+            .into_iter()
+            .collect()
+    );
+}
+
+#[test]
+fn unknown_selector_rejected() {
+    assert!("psh01".parse::<RuleSelector>().is_ok());
+    assert_eq!(
+        "PSX".parse::<RuleSelector>().unwrap_err(),
+        "unknown rule selector 'PSX'"
+    );
+    assert!("".parse::<RuleSelector>().is_err());
+}
+
+#[test]
+fn ignored_rule_not_reported() {
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setup(self):
+        self.widget = Widget()
+"#;
+    let errors = run(src, &Config::default());
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("PSH001"));
+
+    let config = Config {
+        rules: select_rules(None, &[], &selectors(&["PSH001"])),
+        ..Config::default()
+    };
+    assert!(run(src, &config).is_empty());
+}
+
+#[test]
+fn extend_select_enables_opt_in_rule() {
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setUp(self):
+        self.widget = Widget()
+        super(TestWidget, self).setUp()
+"#;
+    assert!(run(src, &Config::default()).is_empty());
+
+    let config = Config {
+        rules: select_rules(None, &selectors(&["PSH007"]), &[]),
+        ..Config::default()
+    };
+    let errors = run(src, &config);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("PSH007"));
+}
+
+#[test]
+fn fix_skips_ignored_rules() {
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setup(self):
+        self.widget = Widget()
+
+    def tearDown(self):
+        self.widget.close()
+"#;
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    let config = Config {
+        rules: select_rules(None, &[], &selectors(&["PSH001"])),
+        ..Config::default()
+    };
+    fix_file_with_config(&file_path, &config);
+    let fixed = read_to_string(&file_path).unwrap();
+    assert!(fixed.contains("def setup(self):"));
+    assert!(fixed.contains("super().tearDown()"));
+}
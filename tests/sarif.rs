@@ -0,0 +1,156 @@
+// tests/sarif.rs
+use pytest_super_hooks::{
+    check_file,
+    fix::fix_file,
+    output::{sarif, Run},
+    Diagnostic, Edit, RULES,
+};
+use serde_json::Value;
+use std::fs::{read_to_string, write};
+use tempfile::TempDir;
+
+// Test suite for the SARIF 2.1.0 output and the fix edits it carries
+
+const SRC: &str = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setup(self):
+        self.widget = Widget()
+
+    def helper(self):
+        pass
+
+    def tearDown(self):
+        super().tearDown()
+        self.widget.close()
+"#;
+
+fn run(src: &str) -> (TempDir, Vec<Diagnostic>) {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    let diagnostics = check_file(&file_path)
+        .into_iter()
+        .map(|mut diagnostic| {
+            diagnostic.file = "tests/test.py".into();
+            diagnostic
+        })
+        .collect();
+    (temp, diagnostics)
+}
+
+fn log(diagnostics: &[Diagnostic]) -> Value {
+    serde_json::from_str(&sarif(&Run {
+        files_checked: &[],
+        files_fixed: &[],
+        diagnostics,
+    }))
+    .unwrap()
+}
+
+/// Apply non-overlapping edits to `src`, the way a SARIF consumer would.
+fn apply(src: &str, edits: &[&Edit]) -> String {
+    let mut lines: Vec<String> = src.lines().map(str::to_string).collect();
+    let mut edits = edits.to_vec();
+    edits.sort_by_key(|edit| std::cmp::Reverse(edit.start));
+    for edit in edits {
+        let first = &lines[edit.start.line - 1];
+        let last = &lines[edit.end.line - 1];
+        let before: String = first.chars().take(edit.start.column - 1).collect();
+        let after: String = last.chars().skip(edit.end.column - 1).collect();
+        let replacement = format!("{}{}{}", before, edit.content, after);
+        lines.splice(
+            edit.start.line - 1..edit.end.line,
+            replacement.lines().map(str::to_string),
+        );
+    }
+    let mut applied = lines.join("\n");
+    if src.ends_with('\n') {
+        applied.push('\n');
+    }
+    applied
+}
+
+#[test]
+fn driver_lists_every_rule() {
+    let (_temp, diagnostics) = run(SRC);
+    let log = log(&diagnostics);
+    assert_eq!(log["version"], "2.1.0");
+    let driver = &log["runs"][0]["tool"]["driver"];
+    assert_eq!(driver["name"], "pytest-super-hooks");
+    let rules = driver["rules"].as_array().unwrap();
+    assert_eq!(rules.len(), RULES.len());
+    assert_eq!(rules[1]["id"], "PSH002");
+    assert_eq!(rules[1]["name"], "super-position");
+    assert!(rules[1]["help"]["text"]
+        .as_str()
+        .unwrap()
+        .contains("--super-position"));
+    assert_eq!(rules[6]["defaultConfiguration"]["enabled"], false);
+    assert_eq!(rules[10]["defaultConfiguration"]["level"], "warning");
+}
+
+#[test]
+fn results_have_locations_and_related_locations() {
+    let (_temp, diagnostics) = run(SRC);
+    let log = log(&diagnostics);
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+
+    assert_eq!(results[0]["ruleId"], "PSH001");
+    assert_eq!(results[0]["ruleIndex"], 0);
+    assert_eq!(results[0]["level"], "error");
+    let location = &results[0]["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "tests/test.py");
+    assert_eq!(
+        location["region"],
+        serde_json::json!({"startLine": 5, "startColumn": 9, "endLine": 5, "endColumn": 14})
+    );
+
+    assert_eq!(results[1]["ruleId"], "PSH002");
+    let related = &results[1]["relatedLocations"][0];
+    assert_eq!(related["message"]["text"], "super().tearDown() found here");
+    assert_eq!(related["physicalLocation"]["region"]["startLine"], 12);
+}
+
+#[test]
+fn fixes_replay_the_fixer() {
+    let (temp, diagnostics) = run(SRC);
+    let log = log(&diagnostics);
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    let replacement = &results[0]["fixes"][0]["artifactChanges"][0]["replacements"][0];
+    assert_eq!(
+        replacement["deletedRegion"],
+        serde_json::json!({"startLine": 5, "startColumn": 1, "endLine": 6, "endColumn": 31})
+    );
+    assert_eq!(
+        replacement["insertedContent"]["text"],
+        "    def setUp(self):\n        self.widget = Widget()\n        super().setUp()"
+    );
+
+    // Applying every fix's edits gives what --fix writes
+    let edits: Vec<&Edit> = diagnostics
+        .iter()
+        .filter_map(|diagnostic| diagnostic.fix.as_ref())
+        .flat_map(|fix| &fix.edits)
+        .collect();
+    assert_eq!(edits.len(), 2);
+    let file_path = temp.path().join("test.py");
+    assert!(fix_file(&file_path));
+    assert_eq!(apply(SRC, &edits), read_to_string(&file_path).unwrap());
+}
+
+#[test]
+fn fix_without_edits_is_left_out() {
+    let src = r#"
+from unittest import TestCase
+
+class TestWidget(TestCase):
+    def setUp(self): self.widget = Widget()
+"#;
+    let (_temp, diagnostics) = run(src);
+    assert_eq!(diagnostics.len(), 1);
+    let log = log(&diagnostics);
+    assert!(log["runs"][0]["results"][0].get("fixes").is_none());
+}
@@ -0,0 +1,180 @@
+// tests/super_position.rs
+use pytest_super_hooks::{
+    check_file_with_config, fix::fix_file_with_config, Config, SuperPosition,
+};
+use std::fs::{read_to_string, write};
+use tempfile::TempDir;
+
+// Test suite for the per-method super() position policy (first / last / anywhere)
+
+fn config(positions: &[(&str, SuperPosition)]) -> Config {
+    Config {
+        super_positions: positions
+            .iter()
+            .map(|(method, position)| (method.to_string(), *position))
+            .collect(),
+        ..Config::default()
+    }
+}
+
+fn run(src: &str, positions: &[(&str, SuperPosition)]) -> Vec<String> {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    check_file_with_config(&file_path, &config(positions))
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+fn run_fix(src: &str, positions: &[(&str, SuperPosition)]) -> (Vec<String>, String) {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    let config = config(positions);
+    fix_file_with_config(&file_path, &config);
+    let errors = check_file_with_config(&file_path, &config)
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    (errors, read_to_string(&file_path).unwrap())
+}
+
+#[test]
+fn position_parses_from_cli_names() {
+    assert_eq!("first".parse(), Ok(SuperPosition::First));
+    assert_eq!("last".parse(), Ok(SuperPosition::Last));
+    assert_eq!(
+        "anywhere-unconditional".parse(),
+        Ok(SuperPosition::Anywhere)
+    );
+    assert!("middle".parse::<SuperPosition>().is_err());
+}
+
+#[test]
+fn first_position_valid() {
+    let src = r#"
+class T(TestCase):
+    @classmethod
+    def setUpClass(cls):
+        """Create shared fixtures."""
+        super().setUpClass()
+        cls.user = User.objects.create()
+"#;
+    assert!(run(src, &[("setUpClass", SuperPosition::First)]).is_empty());
+}
+
+#[test]
+fn first_position_violation() {
+    let src = r#"
+class T(TestCase):
+    @classmethod
+    def setUpClass(cls):
+        cls.user = User.objects.create()
+        super().setUpClass()
+"#;
+    let errors = run(src, &[("setUpClass", SuperPosition::First)]);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("super().setUpClass() must be the first line"));
+}
+
+#[test]
+fn positions_apply_per_method() {
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        super().setUp()
+        self.value = 1
+
+    def tearDown(self):
+        self.cleanup()
+        super().tearDown()
+"#;
+    assert!(run(src, &[("setUp", SuperPosition::First)]).is_empty());
+
+    let errors = run(src, &[]);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("super().setUp() must be the last line"));
+}
+
+#[test]
+fn anywhere_position_accepts_top_level_call() {
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        self.a = 1
+        super().setUp()
+        self.b = 2
+"#;
+    assert!(run(src, &[("setUp", SuperPosition::Anywhere)]).is_empty());
+}
+
+#[test]
+fn anywhere_position_rejects_conditional_call() {
+    let src = r#"
+class T(TestCase):
+    def setUp(self):
+        self.a = 1
+        if self.a:
+            super().setUp()
+"#;
+    let errors = run(src, &[("setUp", SuperPosition::Anywhere)]);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("super().setUp() must be called unconditionally"));
+}
+
+#[test]
+fn fix_moves_super_first_after_docstring() {
+    let src = r#"class T(TestCase):
+    @classmethod
+    def setUpClass(cls):
+        """Create shared fixtures."""
+        cls.user = User.objects.create()
+        super().setUpClass()
+"#;
+    let (errors, fixed) = run_fix(src, &[("setUpClass", SuperPosition::First)]);
+    assert!(errors.is_empty(), "{:?}", errors);
+    let lines: Vec<&str> = fixed.lines().collect();
+    assert_eq!(lines[3], r#"        """Create shared fixtures.""""#);
+    assert_eq!(lines[4], "        super().setUpClass()");
+    assert_eq!(lines[5], "        cls.user = User.objects.create()");
+}
+
+#[test]
+fn fix_inserts_missing_super_first() {
+    let src = r#"class T(TestCase):
+    def setUp(self):
+        self.value = 1
+"#;
+    let (errors, fixed) = run_fix(src, &[("setUp", SuperPosition::First)]);
+    assert!(errors.is_empty(), "{:?}", errors);
+    let lines: Vec<&str> = fixed.lines().collect();
+    assert_eq!(lines[2], "        super().setUp()");
+    assert_eq!(lines[3], "        self.value = 1");
+}
+
+#[test]
+fn fix_leaves_anywhere_call_in_place() {
+    let src = r#"class T(TestCase):
+    def setUp(self):
+        self.a = 1
+        super().setUp()
+        self.b = 2
+"#;
+    let (errors, fixed) = run_fix(src, &[("setUp", SuperPosition::Anywhere)]);
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert_eq!(fixed, src);
+}
+
+#[test]
+fn fix_leaves_first_call_in_place() {
+    let src = r#"class T(TestCase):
+    def setUp(self):
+        """Set up the widget."""
+        super().setUp()
+        self.a = 1
+"#;
+    let (errors, fixed) = run_fix(src, &[("setUp", SuperPosition::First)]);
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert_eq!(fixed, src);
+}
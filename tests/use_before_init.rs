@@ -0,0 +1,208 @@
+// tests/use_before_init.rs
+use pytest_super_hooks::check_file;
+use std::fs::write;
+use tempfile::TempDir;
+
+// Test suite for attributes read in a subclass's setUp before the ancestors' setUp assigns them
+
+fn run(src: &str) -> Vec<String> {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    check_file(&file_path)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+const BASE: &str = r#"
+from unittest import TestCase
+
+class ApiBase(TestCase):
+    def setUp(self):
+        self.client = Client()
+        self.user = make_user()
+        super().setUp()
+"#;
+
+#[test]
+fn read_of_ancestor_attribute_flagged() {
+    let src = format!(
+        "{}{}",
+        BASE,
+        r#"
+class TestApi(ApiBase):
+    def setUp(self):
+        self.response = self.client.get("/")
+        super().setUp()
+"#
+    );
+    let errors = run(&src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains(
+        ":12:25 PSH017 self.client is read before super().setUp() assigns it in ApiBase.setUp ("
+    ));
+    assert!(errors[0].contains("test.py:6)"));
+}
+
+#[test]
+fn each_attribute_reported_once() {
+    let src = format!(
+        "{}{}",
+        BASE,
+        r#"
+class TestApi(ApiBase):
+    def setUp(self):
+        self.client.login(self.user)
+        self.client.get("/")
+        super().setUp()
+"#
+    );
+    let errors = run(&src);
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].contains("self.client is read"));
+    assert!(errors[1].contains("self.user is read"));
+}
+
+#[test]
+fn attribute_assigned_first_accepted() {
+    let src = format!(
+        "{}{}",
+        BASE,
+        r#"
+class TestApi(ApiBase):
+    def setUp(self):
+        self.user = make_admin()
+        self.token = self.user.token
+        super().setUp()
+"#
+    );
+    // ApiBase.setUp overwrites self.user, which the clobbering rule reports instead
+    let errors = run(&src);
+    assert!(errors.iter().all(|error| !error.contains("is read before")));
+}
+
+#[test]
+fn read_after_super_accepted() {
+    let src = format!(
+        "{}{}",
+        BASE,
+        r#"
+class TestApi(ApiBase):
+    def setUp(self):
+        if self.fast:
+            super().setUp()
+        else:
+            super().setUp()
+        self.response = self.client.get("/")
+"#
+    );
+    let errors = run(&src);
+    assert!(errors.iter().all(|error| !error.contains("is read before")));
+}
+
+#[test]
+fn class_attribute_default_accepted() {
+    let src = format!(
+        "{}{}",
+        BASE,
+        r#"
+class TestApi(ApiBase):
+    client = None
+
+    def setUp(self):
+        if self.client is None:
+            self.fallback = True
+        super().setUp()
+"#
+    );
+    assert!(run(&src).is_empty());
+}
+
+#[test]
+fn attribute_from_set_up_class_accepted() {
+    let src = format!(
+        "{}{}",
+        BASE,
+        r#"
+class TestApi(ApiBase):
+    @classmethod
+    def setUpClass(cls):
+        cls.client = SharedClient()
+        super().setUpClass()
+
+    def setUp(self):
+        self.client.reset()
+        super().setUp()
+"#
+    );
+    assert!(run(&src).is_empty());
+}
+
+#[test]
+fn grandparent_attribute_flagged() {
+    let src = format!(
+        "{}{}",
+        BASE,
+        r#"
+class AdminBase(ApiBase):
+    def setUp(self):
+        self.admin = True
+        super().setUp()
+
+class TestAdmin(AdminBase):
+    def setUp(self):
+        self.user.is_staff = True
+        super().setUp()
+"#
+    );
+    let errors = run(&src);
+    assert_eq!(errors.len(), 1);
+    assert!(
+        errors[0].contains("self.user is read before super().setUp() assigns it in ApiBase.setUp")
+    );
+}
+
+#[test]
+fn augmented_assignment_is_a_read() {
+    let src = r#"
+from unittest import TestCase
+
+class CounterBase(TestCase):
+    def setUp(self):
+        self.count = 0
+        super().setUp()
+
+class TestCounter(CounterBase):
+    def setUp(self):
+        self.count += 1
+        super().setUp()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("self.count is read before"));
+}
+
+#[test]
+fn class_fixture_reads_cls_attribute() {
+    let src = r#"
+from django.test import TestCase
+
+class ServerBase(TestCase):
+    @classmethod
+    def setUpClass(cls):
+        cls.server = start_server()
+        super().setUpClass()
+
+class TestServer(ServerBase):
+    @classmethod
+    def setUpClass(cls):
+        cls.url = cls.server.url
+        super().setUpClass()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains(
+        "cls.server is read before super().setUpClass() assigns it in ServerBase.setUpClass"
+    ));
+}
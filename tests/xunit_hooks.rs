@@ -0,0 +1,159 @@
+// tests/xunit_hooks.rs
+use pytest_super_hooks::check_file;
+use std::fs::write;
+use tempfile::TempDir;
+
+// Test suite for pytest xunit-style hooks (setup_method, setup_module, ...)
+
+fn run(src: &str) -> Vec<String> {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("test.py");
+    write(&file_path, src).unwrap();
+    check_file(&file_path)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn xunit_hooks_valid() {
+    let src = r#"
+def setup_module(module):
+    connect()
+
+def teardown_module(module):
+    disconnect()
+
+def setup_function(function):
+    pass
+
+def teardown_function():
+    pass
+
+class TestWidgets:
+    @classmethod
+    def setup_class(cls):
+        cls.factory = WidgetFactory()
+
+    @classmethod
+    def teardown_class(cls):
+        cls.factory.close()
+
+    def setup_method(self, method):
+        self.widget = self.factory.make()
+
+    def teardown_method(self):
+        self.widget.destroy()
+"#;
+    assert!(run(src).is_empty());
+}
+
+#[test]
+fn setup_method_near_miss() {
+    let src = r#"
+class TestWidgets:
+    def setupMethod(self, method):
+        self.widget = Widget()
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("use correct spelling: setup_method"));
+}
+
+#[test]
+fn module_and_class_near_misses() {
+    let src = r#"
+def teardownModule(module):
+    pass
+
+def setup_Function(function):
+    pass
+
+class TestWidgets:
+    def teardown_Class(cls):
+        pass
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 3);
+    assert!(errors[0].contains("use correct spelling: teardown_module"));
+    assert!(errors[1].contains("use correct spelling: setup_function"));
+    assert!(errors[2].contains("use correct spelling: teardown_class"));
+}
+
+#[test]
+fn setup_method_at_module_level() {
+    let src = r#"
+def setup_method(self, method):
+    pass
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("setup_method must be defined inside a test class"));
+}
+
+#[test]
+fn setup_module_inside_class() {
+    let src = r#"
+class TestWidgets:
+    def setup_module(module):
+        pass
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("setup_module must be defined at module level"));
+}
+
+#[test]
+fn setup_method_too_many_parameters() {
+    let src = r#"
+class TestWidgets:
+    def setup_method(self, method, extra):
+        pass
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("setup_method must have the signature setup_method(self, method)"));
+}
+
+#[test]
+fn setup_method_missing_self() {
+    let src = r#"
+class TestWidgets:
+    def setup_method():
+        pass
+"#;
+    assert_eq!(run(src).len(), 1);
+}
+
+#[test]
+fn setup_function_optional_extra_parameters() {
+    let src = r#"
+def setup_function(function, verbose=False, *, retries=3):
+    pass
+"#;
+    assert!(run(src).is_empty());
+}
+
+#[test]
+fn setup_class_required_keyword_only() {
+    let src = r#"
+class TestWidgets:
+    def setup_class(cls, *, factory):
+        pass
+"#;
+    let errors = run(src);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("setup_class must have the signature setup_class(cls)"));
+}
+
+#[test]
+fn unittest_module_hooks_not_near_misses() {
+    let src = r#"
+def setUpModule():
+    pass
+
+def tearDownModule():
+    pass
+"#;
+    assert!(run(src).is_empty());
+}